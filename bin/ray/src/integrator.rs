@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use ray_math::{spectrum::rgb_to_spectrum, Color, HitResult, Hittable, Medium, Ray};
+
+pub const T_MIN: f64 = 0.001;
+
+/// Most times a path may scatter inside media. This is counted separately
+/// from the surface bounce limit, since dense media such as skin or milk
+/// scatter light hundreds of times before it gets out.
+const MAX_VOLUME_DEPTH: usize = 1024;
+
+pub fn ray_color(
+    ray: &Ray,
+    rng: &mut dyn rand::RngCore,
+    world: &dyn Hittable,
+    camera_medium: Option<&Arc<dyn Medium>>,
+    max_depth: usize,
+) -> Color {
+    let hit = world.hit(ray, T_MIN, f64::INFINITY);
+    shade(ray, hit, rng, world, camera_medium, max_depth)
+}
+
+/// Computes the color seen along `ray` given what it hit, which lets camera
+/// rays be intersected as a packet before being shaded one at a time.
+///
+/// The path keeps a stack of the media it is inside, starting with the one the
+/// camera is in. Media are entered and left when the path is transmitted
+/// through a surface with an interior medium. Scattering in a medium doesn't
+/// count towards `max_depth`, which only limits surface bounces.
+///
+/// Path segments that carry a wavelength, from the camera or from a surface
+/// that picked one, are traced spectrally: every RGB albedo, emission and sky
+/// color along them is turned into its value at that wavelength, media sample
+/// with their coefficients' values at it (and so aren't converted again), and
+/// the radiance at it is carried in all three channels.
+pub fn shade(
+    ray: &Ray,
+    hit: Option<HitResult>,
+    rng: &mut dyn rand::RngCore,
+    world: &dyn Hittable,
+    camera_medium: Option<&Arc<dyn Medium>>,
+    max_depth: usize,
+) -> Color {
+    let mut media: Vec<Arc<dyn Medium>> = camera_medium.into_iter().cloned().collect();
+    let mut ray = *ray;
+    let mut hit = hit;
+    let mut throughput = Color::one();
+    let mut color = Color::zero();
+    let mut volume_depth = 0;
+
+    // If we've exceeded the ray bounce limit, no more light is gathered
+    for _ in 0..max_depth {
+        // The path may scatter in the current medium any number of times
+        // before reaching the next surface
+        while let Some(medium) = media.last() {
+            let t_max = hit.as_ref().map_or(f64::INFINITY, |hit| hit.t());
+            let sample = medium.sample(rng, &ray, t_max);
+            // Media already evaluate their coefficients at the ray's
+            // wavelength, so their samples are used as they are
+            color += throughput * sample.emission;
+            throughput *= sample.weight;
+
+            let t = match sample.scatter_t {
+                Some(t) => t,
+                None => break,
+            };
+            volume_depth += 1;
+            if volume_depth > MAX_VOLUME_DEPTH {
+                return color;
+            }
+            let direction = medium.sample_phase(rng, &ray.direction());
+            ray = with_wavelength_of(Ray::new(ray.at(t), direction, ray.time()), &ray);
+            hit = world.hit(&ray, T_MIN, f64::INFINITY);
+        }
+
+        let surface = match hit {
+            Some(surface) => surface,
+            None => {
+                color += throughput * lift(background(&ray), &ray);
+                break;
+            }
+        };
+
+        color += throughput * lift(surface.material().emitted(&surface, &ray), &ray);
+        let scatter_result = match surface.material().scatter(rng, &surface, &ray) {
+            Some(scatter_result) => scatter_result,
+            None => break,
+        };
+
+        // Passing through the surface moves into or out of its interior
+        let transmitted = scatter_result
+            .scattered
+            .direction()
+            .dot(&surface.geometric_normal())
+            < 0.0;
+        let interior = surface
+            .interior_medium()
+            .or_else(|| surface.material().interior_medium());
+        if let (true, Some(interior)) = (transmitted, interior) {
+            cross_interface(&mut media, interior, surface.front_face());
+        }
+
+        // Lifted at the wavelength the light arrived with: a surface that
+        // picks the path's wavelength (e.g. a dispersive one) already weights
+        // its attenuation for it in RGB
+        throughput *= lift(scatter_result.attenuation, &ray);
+        ray = with_wavelength_of(scatter_result.scattered, &ray);
+        hit = world.hit(&ray, T_MIN, f64::INFINITY);
+    }
+
+    color
+}
+
+/// Updates the stack of media a path is inside as it is transmitted through a
+/// surface with `interior` inside it: entering through the front face pushes
+/// it, and leaving through the back removes its innermost entry, which need not
+/// be the top of the stack when volumes overlap.
+fn cross_interface(media: &mut Vec<Arc<dyn Medium>>, interior: &Arc<dyn Medium>, front_face: bool) {
+    if front_face {
+        media.push(Arc::clone(interior));
+    } else if let Some(index) = media.iter().rposition(|m| Arc::ptr_eq(m, interior)) {
+        media.remove(index);
+    }
+}
+
+/// `rgb` as seen by `ray`: its value at the ray's wavelength in every channel,
+/// or unchanged if the ray carries all of them.
+fn lift(rgb: Color, ray: &Ray) -> Color {
+    match ray.wavelength() {
+        Some(wavelength) => Color::one() * rgb_to_spectrum(&rgb, wavelength),
+        None => rgb,
+    }
+}
+
+/// `next` carrying the wavelength of the path segment before it, unless it
+/// picked its own. Once a path takes on a single wavelength it keeps it.
+fn with_wavelength_of(next: Ray, previous: &Ray) -> Ray {
+    match (next.wavelength(), previous.wavelength()) {
+        (None, Some(wavelength)) => next.with_wavelength(wavelength),
+        _ => next,
+    }
+}
+
+fn background(ray: &Ray) -> Color {
+    let unit_direction = ray.direction().normalized();
+    let t = 0.5 * (unit_direction.y() + 1.0);
+    Color::lerp(Color::one(), Color::new(0.5, 0.7, 1.0), t)
+}
+
+#[cfg(test)]
+mod tests {
+    use ray_math::{
+        material::{Dielectric, Material, Subsurface},
+        HomogeneousMedium,
+    };
+
+    use super::*;
+
+    #[test]
+    fn nested_and_overlapping_media() {
+        let air: Arc<dyn Medium> =
+            Arc::new(HomogeneousMedium::new(Color::zero(), Color::one() * 0.01));
+        let glass = Dielectric::new(1.5).with_absorption(Color::new(0.1, 0.2, 0.3));
+        let skin = Subsurface::new(Color::one() * 0.8, Color::one() * 0.1);
+        let glass = glass.interior_medium().unwrap();
+        let skin = skin.interior_medium().unwrap();
+        let stack = |media: &[Arc<dyn Medium>], expected: &[&Arc<dyn Medium>]| {
+            assert_eq!(media.len(), expected.len());
+            for (medium, expected) in media.iter().zip(expected) {
+                assert!(Arc::ptr_eq(medium, expected));
+            }
+        };
+
+        let mut media = vec![Arc::clone(&air)];
+        // Into the glass, then into skin embedded in it
+        cross_interface(&mut media, glass, true);
+        cross_interface(&mut media, skin, true);
+        stack(&media, &[&air, glass, skin]);
+
+        // Out of the glass where the skin pokes through it, while still in
+        // the skin
+        cross_interface(&mut media, glass, false);
+        stack(&media, &[&air, skin]);
+
+        // Leaving a medium the path never entered changes nothing
+        cross_interface(&mut media, glass, false);
+        stack(&media, &[&air, skin]);
+
+        cross_interface(&mut media, skin, false);
+        stack(&media, &[&air]);
+    }
+}
@@ -8,33 +8,15 @@ use std::{
     time::Duration,
 };
 
+use integrator::{ray_color, shade, T_MIN};
 use rand::Rng;
-use ray_math::{Camera, Color, Hittable, Ray};
+use ray_math::{spectrum::XyzFilm, Camera, Color, Hittable, Ray, PACKET_WIDTH};
 use rayon::prelude::*;
 use scenes::SceneOption;
 
+mod integrator;
 mod scenes;
 
-fn ray_color(ray: &Ray, rng: &mut dyn rand::RngCore, world: &dyn Hittable, depth: usize) -> Color {
-    // If we've exceeded the ray bounce limit, no more light is gathered
-    if depth <= 0 {
-        return Color::zero();
-    }
-
-    if let Some(hit) = world.hit(ray, 0.001, f64::INFINITY) {
-        if let Some(scatter_result) = hit.material().scatter(rng, &hit, ray) {
-            let color = ray_color(&scatter_result.scattered, rng, world, depth - 1);
-            return scatter_result.attenuation * color;
-        }
-
-        return Color::zero();
-    }
-
-    let unit_direction = ray.direction().normalized();
-    let t = 0.5 * (unit_direction.y() + 1.0);
-    Color::lerp(Color::one(), Color::new(0.5, 0.7, 1.0), t)
-}
-
 fn write_image(file: &str) -> std::io::Result<()> {
     println!("Starting");
 
@@ -47,6 +29,9 @@ fn write_image(file: &str) -> std::io::Result<()> {
     let samples_per_pixel = 500;
     let max_depth = 50;
     let motion_time_range = 0.0..1.0;
+    // Trace each sample at a single wavelength and accumulate CIE XYZ, rather
+    // than tracing RGB
+    let spectral = false;
 
     let camera = Camera::new(world.camera);
 
@@ -68,7 +53,7 @@ fn write_image(file: &str) -> std::io::Result<()> {
 
             std::io::stdout().flush().expect("Couldn't flush stdout");
             if done == total {
-                println!("");
+                println!();
                 break;
             }
 
@@ -77,6 +62,7 @@ fn write_image(file: &str) -> std::io::Result<()> {
     };
 
     let root = world.root;
+    let camera_medium = world.camera_medium;
     let mut pixels: Vec<_> = (0..image_height)
         .rev()
         .flat_map(|j| (0..image_width).map(move |i| (i, j)))
@@ -85,19 +71,75 @@ fn write_image(file: &str) -> std::io::Result<()> {
         .map(|(idx, (i, j))| {
             let mut rand = rand::thread_rng();
             let mut pixel_color = Color::zero();
-            for _ in 0..samples_per_pixel {
+            let mut film = XyzFilm::new();
+            let sample_coords = |rand: &mut rand::rngs::ThreadRng| {
                 let u = (i as f64 + rand.gen_range(0.0..=1.0)) / (image_width - 1) as f64;
                 let v = (j as f64 + rand.gen_range(0.0..=1.0)) / (image_height - 1) as f64;
+                (u, v)
+            };
+            let mut sample_index = 0;
+            let mut sample_wavelength = |rand: &mut rand::rngs::ThreadRng, ray: &Ray| {
+                sample_index += 1;
+                let wavelength = spectral
+                    .then(|| XyzFilm::sample_wavelength(rand, sample_index - 1, samples_per_pixel));
+                (
+                    wavelength.map_or(*ray, |w| ray.with_wavelength(w)),
+                    wavelength,
+                )
+            };
+            let mut add_sample = |wavelength: Option<f64>, color: Color| match wavelength {
+                Some(wavelength) => film.add_sample(wavelength, color.x()),
+                None => pixel_color += color,
+            };
+
+            // Samples within a pixel are coherent, so trace their camera rays
+            // together as packets
+            for _ in 0..samples_per_pixel / PACKET_WIDTH {
+                let coords = [(); PACKET_WIDTH].map(|_| sample_coords(&mut rand));
+                let packet = camera.get_ray_packet_defocused(
+                    &mut rand,
+                    Some(motion_time_range.clone()),
+                    coords,
+                );
+                let hits = root.hit_packet(
+                    &packet,
+                    T_MIN,
+                    &[f64::INFINITY; PACKET_WIDTH],
+                    &[true; PACKET_WIDTH],
+                );
+                for (ray, hit) in packet.rays().iter().zip(hits) {
+                    let (ray, wavelength) = sample_wavelength(&mut rand, ray);
+                    let color = shade(
+                        &ray,
+                        hit,
+                        &mut rand,
+                        &root,
+                        camera_medium.as_ref(),
+                        max_depth,
+                    );
+                    add_sample(wavelength, color);
+                }
+            }
+
+            for _ in 0..samples_per_pixel % PACKET_WIDTH {
+                let (u, v) = sample_coords(&mut rand);
                 let ray =
                     camera.get_ray_defocused(&mut rand, Some(motion_time_range.clone()), u, v);
-                pixel_color += ray_color(&ray, &mut rand, &root, max_depth);
+                let (ray, wavelength) = sample_wavelength(&mut rand, &ray);
+                let color = ray_color(&ray, &mut rand, &root, camera_medium.as_ref(), max_depth);
+                add_sample(wavelength, color);
+            }
+
+            // The film converts to RGB once all of its samples are in
+            if spectral {
+                pixel_color = film.rgb() * samples_per_pixel as f64;
             }
 
             let scale = 1.0 / samples_per_pixel as f64;
             let adjusted_color = Color::new(
-                (scale * pixel_color.x()).sqrt(),
-                (scale * pixel_color.y()).sqrt(),
-                (scale * pixel_color.z()).sqrt(),
+                (scale * pixel_color.x()).max(0.0).sqrt(),
+                (scale * pixel_color.y()).max(0.0).sqrt(),
+                (scale * pixel_color.z()).max(0.0).sqrt(),
             );
 
             pixels_done.fetch_add(1, Ordering::Relaxed);
@@ -121,7 +163,7 @@ fn write_image(file: &str) -> std::io::Result<()> {
 }
 
 fn main() {
-    if let Err(_) = write_image("image.ppm") {
+    if write_image("image.ppm").is_err() {
         eprintln!("Failed to generate image");
     }
 }
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::Lambertian,
+    texture::{Perlin, SolidColor},
+    Aabb, BvhNode, CameraConfig, Color, GridMedium, GridMediumConfig, HittableList, Point3, Sphere,
+    StaticTransform, Vec3, VolumeEmission, VoxelGrid,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let texture = Arc::new(SolidColor::new(Color::new(0.5, 0.5, 0.5)));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(texture)),
+    )));
+
+    // A turbulent blob of smoke standing in for a simulation cache, with a hot
+    // core that glows
+    let perlin = Perlin::new(rng);
+    let center = Point3::new(0.5, 0.5, 0.5);
+    let density = VoxelGrid::from_fn([64, 64, 64], |point| {
+        let falloff = 1.0 - (*point - center).length() * 2.0;
+        (falloff + 0.4 * perlin.turbulance(&(*point * 6.0), 5) - 0.3).max(0.0)
+    });
+    let temperature = VoxelGrid::from_fn([32, 32, 32], |point| {
+        let falloff = 1.0 - (*point - center).length() * 3.0;
+        2500.0 * falloff.max(0.0)
+    });
+
+    world.add(Arc::new(GridMedium::new(GridMediumConfig {
+        bounds: Aabb::new(Point3::new(-2.0, 0.0, -2.0), Point3::new(2.0, 4.0, 2.0)),
+        density: Arc::new(density),
+        density_scale: 3.0,
+        albedo: Color::new(0.6, 0.6, 0.6),
+        emission: VolumeEmission::Temperature {
+            kelvin: Arc::new(temperature),
+            intensity: 20000.0,
+        },
+    })));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(13.0, 3.0, 3.0),
+            look_at: Point3::new(0.0, 2.0, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 25.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
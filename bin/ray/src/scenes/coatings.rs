@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Conductor, ConductorPreset, Lambertian, Layered, Material},
+    texture::{Checkered, Noise, SolidColor},
+    BvhNode, CameraConfig, Color, Cuboid, HittableList, Point3, Sphere, StaticTransform, Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    // Bare bases at the front, the same bases coated behind them
+    let paint = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.7, 0.05, 0.05,
+    )))));
+    let brushed_gold = Arc::new(Conductor::preset(ConductorPreset::Gold, 0.5));
+    let grain = Arc::new(Lambertian::new(Arc::new(Noise::new(rng, 8.0))));
+    let bases: [Arc<dyn Material>; 3] = [paint, brushed_gold, grain];
+    for (column, base) in bases.iter().enumerate() {
+        let z = 1.2 * (column as f64 - 1.0);
+        world.add(Arc::new(Sphere::from(
+            StaticTransform::new(Point3::new(1.2, 0.5, z)),
+            0.5,
+            Arc::clone(base),
+        )));
+    }
+
+    // Car paint: a glossy clear coat over red
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, 0.5, -1.2)),
+        0.5,
+        Arc::new(Layered::uniform(Arc::clone(&bases[0]), 1.5, 0.02)),
+    )));
+    // Lacquered gold: a smooth coat over a rough metal
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, 0.5, 0.0)),
+        0.5,
+        Arc::new(Layered::uniform(Arc::clone(&bases[1]), 1.5, 0.05)),
+    )));
+    // Varnished wood: a thick amber layer over the grain
+    let varnish = Color::new(0.3, 1.2, 4.0);
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, 0.5, 1.2)),
+        0.5,
+        Arc::new(Layered::uniform(Arc::clone(&bases[2]), 1.5, 0.1).with_absorption(varnish, 0.3)),
+    )));
+    world.add(Arc::new(Cuboid::from(
+        StaticTransform::new(Point3::new(-1.5, 0.15, 0.0)),
+        Vec3::new(1.2, 0.3, 3.6),
+        Arc::new(Layered::uniform(Arc::clone(&bases[2]), 1.5, 0.05).with_absorption(varnish, 0.3)),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(5.0, 2.5, 1.5),
+            look_at: Point3::new(0.0, 0.4, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 40.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Dielectric, Lambertian},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, Cuboid, HittableList, Point3, Quad, Sphere, StaticTransform,
+    Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    // The same green glass in balls of different sizes: bigger ones are
+    // darker and deeper in color
+    let green = Color::new(1.2, 0.15, 0.9);
+    for (i, &radius) in [0.25, 0.5, 0.8].iter().enumerate() {
+        world.add(Arc::new(Sphere::from(
+            StaticTransform::new(Point3::new(0.0, radius, 2.0 - 1.6 * i as f64)),
+            radius,
+            Arc::new(Dielectric::new(1.5).with_absorption(green)),
+        )));
+    }
+
+    // An amber slab, thickest seen edge on
+    world.add(Arc::new(Cuboid::from(
+        StaticTransform::new(Point3::new(-1.5, 0.6, 1.5)),
+        Vec3::new(0.3, 1.2, 1.2),
+        Arc::new(Dielectric::new(1.5).with_absorption(Color::new(0.1, 0.6, 2.0))),
+    )));
+
+    // A thin pane of blue tinted glass, a single quad
+    world.add(Arc::new(Quad::from(
+        StaticTransform::new(Point3::new(-1.5, 0.0, -1.5)),
+        Point3::new(0.0, 0.0, -0.8),
+        Vec3::new(0.0, 1.6, 0.0),
+        Vec3::new(0.0, 0.0, 1.6),
+        Arc::new(Dielectric::new(1.0).with_tint(Color::new(0.5, 0.7, 1.0))),
+    )));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(-3.0, 0.5, -1.5)),
+        0.5,
+        Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+            0.9, 0.9, 0.9,
+        ))))),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(6.0, 2.5, 0.5),
+            look_at: Point3::new(-0.8, 0.5, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 40.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
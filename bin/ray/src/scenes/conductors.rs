@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Conductor, ConductorPreset, Lambertian},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, HittableList, Point3, Sphere, StaticTransform, Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    // Each metal in a row, getting rougher towards the back
+    let presets = [
+        ConductorPreset::Gold,
+        ConductorPreset::Silver,
+        ConductorPreset::Copper,
+        ConductorPreset::Aluminium,
+        ConductorPreset::Iron,
+    ];
+    for (row, &preset) in presets.iter().enumerate() {
+        for (column, &roughness) in [0.05, 0.3, 0.6].iter().enumerate() {
+            world.add(Arc::new(Sphere::from(
+                StaticTransform::new(Point3::new(
+                    -1.1 * column as f64,
+                    0.45,
+                    1.1 * (row as f64 - 2.0),
+                )),
+                0.45,
+                Arc::new(Conductor::preset(preset, roughness)),
+            )));
+        }
+    }
+
+    // Roughness from a texture: polished and brushed checks
+    let roughness = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::one() * 0.05)),
+        Arc::new(SolidColor::new(Color::one() * 0.5)),
+    ));
+    let (eta, k) = ConductorPreset::Gold.index_of_refraction();
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(-4.0, 1.0, 0.0)),
+        1.0,
+        Arc::new(Conductor::new(eta, k, roughness)),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(6.0, 3.0, 0.0),
+            look_at: Point3::new(-1.5, 0.5, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 45.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
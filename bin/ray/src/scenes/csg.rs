@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Dielectric, Lambertian, Metal},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, Csg, CsgOperation, Cuboid, Cylinder, HittableList, Point3,
+    Sphere, StaticTransform, Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let texture = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(texture)),
+    )));
+
+    let red = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.7, 0.1, 0.1,
+    )))));
+    let white = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.9, 0.9, 0.9,
+    )))));
+    let steel = Arc::new(Metal::new(Color::new(0.7, 0.7, 0.75), 0.2));
+
+    // Rounded cube with a hole drilled through it
+    let center = Point3::new(0.0, 1.0, 0.0);
+    let rounded = Arc::new(Csg::new(
+        CsgOperation::Intersection,
+        Arc::new(Cuboid::from(
+            StaticTransform::new(center),
+            Vec3::new(2.0, 2.0, 2.0),
+            red,
+        )),
+        Arc::new(Sphere::from(
+            StaticTransform::new(center),
+            1.35,
+            white.clone(),
+        )),
+    ));
+    world.add(Arc::new(Csg::new(
+        CsgOperation::Difference,
+        rounded,
+        Arc::new(Cylinder::from(
+            StaticTransform::new(center),
+            0.5,
+            3.0,
+            true,
+            white,
+        )),
+    )));
+
+    // Metal ball with a bite taken out of it
+    let ball_center = Point3::new(0.0, 1.0, 2.8);
+    world.add(Arc::new(Csg::new(
+        CsgOperation::Difference,
+        Arc::new(Sphere::from(
+            StaticTransform::new(ball_center),
+            1.0,
+            steel.clone(),
+        )),
+        Arc::new(Sphere::from(
+            StaticTransform::new(ball_center + Vec3::new(0.6, 0.5, 0.3)),
+            0.7,
+            steel,
+        )),
+    )));
+
+    // Glass lens: the overlap of two spheres
+    let lens_center = Point3::new(0.5, 1.0, -2.8);
+    let glass = Arc::new(Dielectric::new(1.5));
+    world.add(Arc::new(Csg::new(
+        CsgOperation::Intersection,
+        Arc::new(Sphere::from(
+            StaticTransform::new(lens_center + Vec3::new(1.2, 0.0, 0.0)),
+            1.5,
+            glass.clone(),
+        )),
+        Arc::new(Sphere::from(
+            StaticTransform::new(lens_center - Vec3::new(1.2, 0.0, 0.0)),
+            1.5,
+            glass,
+        )),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(10.0, 6.0, 3.0),
+            look_at: Point3::new(0.0, 1.0, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 35.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
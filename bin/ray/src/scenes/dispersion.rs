@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Dielectric, Dispersion, Lambertian},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, HittableList, Mesh, MeshObject, Point3, Sphere, StaticTransform,
+    Vec3,
+};
+
+use super::SceneConfig;
+
+/// A triangular prism lying along Z, `width` across its base and `height`
+/// tall, with flat faces.
+fn prism(width: f64, height: f64, length: f64) -> Mesh {
+    let (w, h) = (0.5 * width, 0.5 * length);
+    Mesh::new(
+        vec![
+            Point3::new(-w, 0.0, h),
+            Point3::new(w, 0.0, h),
+            Point3::new(0.0, height, h),
+            Point3::new(-w, 0.0, -h),
+            Point3::new(w, 0.0, -h),
+            Point3::new(0.0, height, -h),
+        ],
+        vec![
+            [0, 1, 2],
+            [3, 5, 4],
+            [0, 3, 4],
+            [0, 4, 1],
+            [1, 4, 5],
+            [1, 5, 2],
+            [0, 2, 5],
+            [0, 5, 3],
+        ],
+    )
+}
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.05, 0.05, 0.05))),
+        Arc::new(SolidColor::new(Color::new(0.95, 0.95, 0.95))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    // A flint glass prism, the sky's edge split into colors through it
+    world.add(Arc::new(MeshObject::from(
+        rng,
+        StaticTransform::new(Point3::new(0.0, 0.0, 0.0)),
+        Arc::new(prism(1.6, 1.4, 3.0)),
+        Arc::new(Dielectric::new(1.5).with_dispersion(Dispersion::SF11)),
+    )));
+
+    // Diamond, and a made up glass dispersing far more than any real one
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(1.0, 0.6, 2.3)),
+        0.6,
+        Arc::new(Dielectric::new(2.4).with_dispersion(Dispersion::DIAMOND)),
+    )));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(1.0, 0.6, -2.3)),
+        0.6,
+        Arc::new(Dielectric::new(1.5).with_dispersion(Dispersion::Cauchy { a: 1.4, b: 0.05 })),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(7.0, 1.6, 0.0),
+            look_at: Point3::new(0.0, 0.7, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 45.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
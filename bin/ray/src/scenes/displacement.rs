@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::Lambertian,
+    texture::{SolidColor, Turbulence},
+    BvhNode, CameraConfig, Color, HittableList, Mesh, MeshObject, Point3, StaticTransform, Vec3,
+};
+
+use super::SceneConfig;
+
+/// A cube from -1 to 1 with its corner normals averaged, so displacement
+/// along them rounds it off into a rock.
+fn cube_mesh() -> Mesh {
+    let positions = (0..8)
+        .map(|i| {
+            Point3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            )
+        })
+        .collect();
+    let faces = [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ];
+    let triangles = faces
+        .iter()
+        .flat_map(|&[a, b, c, d]| [[a, b, c], [a, c, d]])
+        .collect();
+    Mesh::new(positions, triangles).with_smooth_normals()
+}
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    // A patch of ground, roughened by displacement rather than bump mapping
+    // so it keeps its bumps at grazing angles and along its silhouette
+    let ground = Mesh::new(
+        vec![
+            Point3::new(-4.0, 0.0, -4.0),
+            Point3::new(4.0, 0.0, -4.0),
+            Point3::new(4.0, 0.0, 4.0),
+            Point3::new(-4.0, 0.0, 4.0),
+        ],
+        vec![[0, 2, 1], [0, 3, 2]],
+    )
+    .with_smooth_normals()
+    .tessellated(0.05)
+    .displaced(&Turbulence::new(rng, 1.5), 0.4);
+    world.add(Arc::new(MeshObject::from(
+        rng,
+        StaticTransform::new(Point3::zero()),
+        Arc::new(ground),
+        Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+            0.45, 0.4, 0.3,
+        ))))),
+    )));
+
+    let rock = cube_mesh()
+        .tessellated(0.04)
+        .displaced(&Turbulence::new(rng, 1.2), 0.6);
+    world.add(Arc::new(MeshObject::from(
+        rng,
+        StaticTransform::new(Point3::new(0.0, 1.0, 0.0)),
+        Arc::new(rock),
+        Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+            0.5, 0.5, 0.52,
+        ))))),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(5.0, 2.5, 3.0),
+            look_at: Point3::new(0.0, 0.9, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 40.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
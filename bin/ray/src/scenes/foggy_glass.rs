@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Dielectric, Lambertian, PassThrough},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, HittableList, HomogeneousMedium, Medium, MediumBoundary, Point3,
+    Sphere, StaticTransform, Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let texture = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(texture)),
+    )));
+
+    // Glass ball filled with an orange, slightly glowing fog
+    let glass = Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, 1.5, 0.0)),
+        1.5,
+        Arc::new(Dielectric::new(1.5)),
+    ));
+    let fog = HomogeneousMedium::new(Color::new(0.05, 0.2, 0.6), Color::new(1.5, 1.2, 0.8))
+        .with_emission(Color::new(0.5, 0.2, 0.05))
+        .with_asymmetry(0.3);
+    world.add(Arc::new(MediumBoundary::new(glass, Arc::new(fog))));
+
+    let texture = Arc::new(SolidColor::new(Color::new(0.4, 0.2, 0.1)));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(-4.0, 1.0, -1.0)),
+        1.0,
+        Arc::new(Lambertian::new(texture)),
+    )));
+
+    // The whole scene, camera included, sits in a thin haze that ends at an
+    // invisible boundary so the sky can still be seen
+    let haze: Arc<dyn Medium> = Arc::new(HomogeneousMedium::new(
+        Color::new(0.002, 0.002, 0.002),
+        Color::new(0.02, 0.02, 0.02),
+    ));
+    let haze_boundary = Arc::new(Sphere::from(
+        StaticTransform::new(Point3::zero()),
+        30.0,
+        Arc::new(PassThrough),
+    ));
+    world.add(Arc::new(MediumBoundary::new(
+        haze_boundary,
+        Arc::clone(&haze),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(13.0, 2.0, 3.0),
+            look_at: Point3::new(0.0, 1.0, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 25.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: Some(haze),
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Lambertian, RoughDielectric},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, Cuboid, HittableList, HomogeneousMedium, MediumBoundary, Point3,
+    Sphere, StaticTransform, Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    // Glass panes getting frostier from left to right, in front of a ball to
+    // see through them
+    for (i, &roughness) in [0.0, 0.1, 0.25, 0.5].iter().enumerate() {
+        let z = 1.4 * (i as f64 - 1.5);
+        world.add(Arc::new(Cuboid::from(
+            StaticTransform::new(Point3::new(0.0, 0.8, z)),
+            Vec3::new(0.08, 1.6, 1.2),
+            Arc::new(RoughDielectric::uniform(1.5, roughness)),
+        )));
+    }
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(-2.0, 0.6, 0.0)),
+        0.6,
+        Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+            0.8, 0.2, 0.1,
+        ))))),
+    )));
+
+    // Frosted and clear checks on one ball
+    let roughness = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::one() * 0.02)),
+        Arc::new(SolidColor::new(Color::one() * 0.4)),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(1.5, 0.7, -2.5)),
+        0.7,
+        Arc::new(RoughDielectric::new(1.5, roughness)),
+    )));
+
+    // A translucent plastic: a rough surface over a scattering interior
+    let plastic = Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(1.5, 0.7, 2.5)),
+        0.7,
+        Arc::new(RoughDielectric::uniform(1.46, 0.3)),
+    ));
+    let interior = HomogeneousMedium::new(Color::new(0.05, 0.3, 0.6), Color::new(8.0, 8.0, 8.0));
+    world.add(Arc::new(MediumBoundary::new(plastic, Arc::new(interior))));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(7.0, 2.5, 0.0),
+            look_at: Point3::new(0.0, 0.8, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 45.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
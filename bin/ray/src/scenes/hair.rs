@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use ray_math::{
+    material::{Hair, Lambertian},
+    texture::SolidColor,
+    BvhNode, CameraConfig, Color, Curve, CurveShape, HittableList, Point3, Sphere, StaticTransform,
+    Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+            0.35, 0.25, 0.15,
+        ))))),
+    )));
+
+    // A ball of fur: strands grow out of the skin and droop under their own
+    // weight
+    let center = Point3::new(0.0, 1.0, 0.0);
+    let radius = 0.7;
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(center),
+        radius,
+        Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+            0.1, 0.06, 0.04,
+        ))))),
+    )));
+    let fur = Arc::new(Hair::from_melanin(1.3, 0.4).with_roughness(0.25, 0.3));
+    for _ in 0..6000 {
+        let normal = Vec3::random_unit(rng);
+        let length = rng.gen_range(0.3..0.4);
+        let droop = Vec3::new(0.0, -1.0, 0.0) + 0.3 * Vec3::random_in_unit_sphere(rng);
+        let root = normal * radius;
+        world.add(Arc::new(Curve::from(
+            StaticTransform::new(center),
+            [
+                root,
+                root + normal * (length / 3.0),
+                root + normal * (2.0 * length / 3.0) + droop * (0.15 * length),
+                root + normal * length + droop * (0.45 * length),
+            ],
+            (0.012, 0.002),
+            CurveShape::Cylinder,
+            fur.clone(),
+        )));
+    }
+
+    // A patch of grass blades as flat ribbons that taper to a point
+    let grass = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.25, 0.55, 0.15,
+    )))));
+    for _ in 0..4000 {
+        let root = Point3::new(rng.gen_range(-3.0..0.0), 0.0, rng.gen_range(-2.5..2.5));
+        if (root - Point3::new(0.0, 0.0, 0.0)).length() < 1.0 {
+            continue;
+        }
+        let height = rng.gen_range(0.3..0.7);
+        let bend = rng.gen_range(0.0..0.3) * Vec3::new(rng.gen_range(-1.0..1.0), 0.0, 1.0);
+        world.add(Arc::new(Curve::from(
+            StaticTransform::new(root),
+            [
+                Point3::zero(),
+                Point3::new(0.0, height / 3.0, 0.0),
+                Point3::new(0.0, 2.0 * height / 3.0, 0.0) + 0.4 * bend,
+                Point3::new(0.0, height, 0.0) + bend,
+            ],
+            (0.03, 0.0),
+            CurveShape::Flat,
+            grass.clone(),
+        )));
+    }
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(5.0, 2.0, 2.0),
+            look_at: Point3::new(-0.5, 0.8, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 35.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
@@ -0,0 +1,101 @@
+use std::{f64, sync::Arc};
+
+use ray_math::{
+    material::{Lambertian, Metal},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, HittableList, Mesh, MeshObject, Point3, Sphere, StaticTransform,
+    Vec3,
+};
+
+use super::SceneConfig;
+
+/// A coarse latitude/longitude sphere, so the difference between flat and
+/// smooth shading is easy to see.
+fn sphere_mesh(radius: f64, segments: usize, rings: usize) -> Mesh {
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    for ring in 0..=rings {
+        let v = ring as f64 / rings as f64;
+        let theta = v * f64::consts::PI;
+        for segment in 0..=segments {
+            let u = segment as f64 / segments as f64;
+            let phi = u * 2.0 * f64::consts::PI;
+            positions.push(
+                radius
+                    * Point3::new(
+                        -theta.sin() * phi.cos(),
+                        -theta.cos(),
+                        theta.sin() * phi.sin(),
+                    ),
+            );
+            uvs.push((u, v));
+        }
+    }
+
+    let mut triangles = Vec::new();
+    let row = segments + 1;
+    for ring in 0..rings {
+        for segment in 0..segments {
+            let a = ring * row + segment;
+            let (b, c, d) = (a + 1, a + row + 1, a + row);
+            triangles.push([a, b, c]);
+            triangles.push([a, c, d]);
+        }
+    }
+
+    Mesh::new(positions, triangles).with_uvs(uvs)
+}
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    let red = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.7, 0.15, 0.1,
+    )))));
+    let flat = Arc::new(sphere_mesh(1.0, 16, 8));
+    world.add(Arc::new(MeshObject::from(
+        rng,
+        StaticTransform::new(Point3::new(0.0, 1.0, -2.4)),
+        flat,
+        red.clone(),
+    )));
+
+    let smooth = Arc::new(sphere_mesh(1.0, 16, 8).with_smooth_normals());
+    world.add(Arc::new(MeshObject::from(
+        rng,
+        StaticTransform::new(Point3::new(0.0, 1.0, 0.0)),
+        smooth.clone(),
+        red,
+    )));
+    world.add(Arc::new(MeshObject::from(
+        rng,
+        StaticTransform::new(Point3::new(0.0, 1.0, 2.4)),
+        smooth,
+        Arc::new(Metal::new(Color::new(0.8, 0.8, 0.8), 0.0)),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(9.0, 3.0, 0.0),
+            look_at: Point3::new(0.0, 1.0, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 40.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
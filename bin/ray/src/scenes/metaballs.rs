@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use ray_math::{
+    material::{Dielectric, Lambertian, Metal},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, FieldSource, HittableList, Metaballs, Point3, Sphere,
+    StaticTransform, Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    // Drops pulling apart from a puddle, as if dripping upwards
+    let mut drops = vec![FieldSource::new(Point3::new(0.0, 0.0, 0.0), 1.6, 1.0)];
+    for (i, height) in [0.9, 1.6, 2.2, 2.7].iter().enumerate() {
+        drops.push(FieldSource::new(
+            Point3::new(0.0, *height, 0.0),
+            0.9 - 0.12 * i as f64,
+            1.0,
+        ));
+    }
+    world.add(Arc::new(Metaballs::from(
+        StaticTransform::new(Point3::new(0.0, 0.0, 0.0)),
+        drops,
+        0.35,
+        Arc::new(Dielectric::new(1.33)),
+    )));
+
+    // A cluster of random blobs with a hollow carved out by a negative source
+    let mut cluster: Vec<FieldSource> = (0..10)
+        .map(|_| FieldSource::new(Vec3::random(rng, -0.6, 0.6), rng.gen_range(0.5..0.8), 1.0))
+        .collect();
+    cluster.push(FieldSource::new(Point3::new(0.4, 0.3, 0.6), 0.6, -1.5));
+    world.add(Arc::new(Metaballs::from(
+        StaticTransform::new(Point3::new(-0.5, 1.0, -2.5)),
+        cluster,
+        0.3,
+        Arc::new(Metal::new(Color::new(0.9, 0.7, 0.4), 0.05)),
+    )));
+
+    // Two drops about to merge, next to the spheres they'd be without merging
+    let clay = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.75, 0.3, 0.3,
+    )))));
+    world.add(Arc::new(Metaballs::from(
+        StaticTransform::new(Point3::new(0.5, 0.5, 2.3)),
+        vec![
+            FieldSource::new(Point3::new(-0.45, 0.0, 0.0), 0.9, 1.0),
+            FieldSource::new(Point3::new(0.45, 0.0, 0.0), 0.9, 1.0),
+        ],
+        0.3,
+        clay,
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(9.0, 3.0, 1.0),
+            look_at: Point3::new(0.0, 1.0, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 35.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
@@ -1,18 +1,65 @@
-use ray_math::{BvhNode, CameraConfig};
+use std::sync::Arc;
 
+use ray_math::{BvhNode, CameraConfig, Medium};
+
+mod cloud;
+mod coatings;
+mod colored_glass;
+mod conductors;
+mod csg;
+mod dispersion;
+mod displacement;
+mod foggy_glass;
+mod frosted_glass;
+mod hair;
+mod meshes;
+mod metaballs;
+mod normal_maps;
+mod particles;
+mod primitives;
+mod principled;
 mod random;
+mod sdf;
+mod smoke;
+mod subdivision;
+mod subsurface;
+mod terrain;
 mod two_perlin_spheres;
 mod two_spheres;
 
+#[allow(dead_code)]
 pub enum SceneOption {
     Random,
     TwoSpheres,
     TwoPerlinSpheres,
+    Smoke,
+    Cloud,
+    FoggyGlass,
+    Primitives,
+    Csg,
+    Sdf,
+    Terrain,
+    Meshes,
+    NormalMaps,
+    Displacement,
+    Subdivision,
+    Hair,
+    Particles,
+    Metaballs,
+    Conductors,
+    FrostedGlass,
+    ColoredGlass,
+    Dispersion,
+    Principled,
+    Coatings,
+    Subsurface,
 }
 
 pub struct SceneConfig {
     pub root: BvhNode,
     pub camera: CameraConfig,
+    /// The medium the camera is inside, if any.
+    pub camera_medium: Option<Arc<dyn Medium>>,
 }
 
 pub fn make_scene(rng: &mut dyn rand::RngCore, scene: SceneOption) -> SceneConfig {
@@ -20,5 +67,26 @@ pub fn make_scene(rng: &mut dyn rand::RngCore, scene: SceneOption) -> SceneConfi
         SceneOption::Random => random::scene(rng),
         SceneOption::TwoSpheres => two_spheres::scene(rng),
         SceneOption::TwoPerlinSpheres => two_perlin_spheres::scene(rng),
+        SceneOption::Smoke => smoke::scene(rng),
+        SceneOption::Cloud => cloud::scene(rng),
+        SceneOption::FoggyGlass => foggy_glass::scene(rng),
+        SceneOption::Primitives => primitives::scene(rng),
+        SceneOption::Csg => csg::scene(rng),
+        SceneOption::Sdf => sdf::scene(rng),
+        SceneOption::Terrain => terrain::scene(rng),
+        SceneOption::Meshes => meshes::scene(rng),
+        SceneOption::NormalMaps => normal_maps::scene(rng),
+        SceneOption::Displacement => displacement::scene(rng),
+        SceneOption::Subdivision => subdivision::scene(rng),
+        SceneOption::Hair => hair::scene(rng),
+        SceneOption::Particles => particles::scene(rng),
+        SceneOption::Metaballs => metaballs::scene(rng),
+        SceneOption::Conductors => conductors::scene(rng),
+        SceneOption::FrostedGlass => frosted_glass::scene(rng),
+        SceneOption::ColoredGlass => colored_glass::scene(rng),
+        SceneOption::Dispersion => dispersion::scene(rng),
+        SceneOption::Principled => principled::scene(rng),
+        SceneOption::Coatings => coatings::scene(rng),
+        SceneOption::Subsurface => subsurface::scene(rng),
     }
 }
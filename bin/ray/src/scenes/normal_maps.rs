@@ -0,0 +1,80 @@
+use std::{f64, sync::Arc};
+
+use ray_math::{
+    material::{Lambertian, Metal, NormalMap},
+    texture::{Noise, SolidColor, Texture, Turbulence},
+    BvhNode, CameraConfig, Color, HittableList, Point3, Quad, Sphere, StaticTransform, Vec3,
+};
+
+use super::SceneConfig;
+
+/// A tangent-space normal map of circular ripples spreading out from the
+/// middle of the UV square.
+struct Ripples {
+    frequency: f64,
+    amplitude: f64,
+}
+
+impl Texture for Ripples {
+    fn value(&self, uv: (f64, f64), _point: &Point3) -> Color {
+        let (x, y) = (uv.0 - 0.5, uv.1 - 0.5);
+        let radius = (x * x + y * y).sqrt().max(1e-6);
+        let slope = self.amplitude * (self.frequency * radius).cos();
+        let normal = Vec3::new(-slope * x / radius, -slope * y / radius, 1.0).normalized();
+        0.5 * (normal + Color::one())
+    }
+}
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let ripples = Arc::new(Ripples {
+        frequency: 120.0,
+        amplitude: 0.6,
+    });
+    let floor = Lambertian::new(Arc::new(SolidColor::new(Color::new(0.5, 0.55, 0.6))))
+        .with_normal_map(NormalMap::TangentSpace(ripples));
+    world.add(Arc::new(Quad::from(
+        StaticTransform::new(Point3::zero()),
+        Point3::new(-10.0, 0.0, 10.0),
+        Vec3::new(20.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, -20.0),
+        Arc::new(floor),
+    )));
+
+    let rough = Lambertian::new(Arc::new(SolidColor::new(Color::new(0.8, 0.5, 0.3))))
+        .with_normal_map(NormalMap::Bump {
+            height: Arc::new(Turbulence::new(rng, 4.0)),
+            strength: 0.05,
+        });
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, 1.0, -1.2)),
+        1.0,
+        Arc::new(rough),
+    )));
+
+    let hammered = Metal::new(Color::new(0.85, 0.85, 0.8), 0.0).with_normal_map(NormalMap::Bump {
+        height: Arc::new(Noise::new(rng, 4.0)),
+        strength: 0.02,
+    });
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, 1.0, 1.2)),
+        1.0,
+        Arc::new(hammered),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(8.0, 3.0, 0.0),
+            look_at: Point3::new(0.0, 0.8, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 35.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
@@ -0,0 +1,96 @@
+use std::{f64::consts::PI, sync::Arc};
+
+use rand::Rng;
+
+use ray_math::{
+    material::{Lambertian, Metal},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, HittableList, ParticleSystem, Point3, Sphere, StaticTransform,
+    Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    // A spiral galaxy of small diffuse particles, colored from a hot core to
+    // blue arms
+    let count = 200_000;
+    let mut positions = Vec::with_capacity(count);
+    let mut radii = Vec::with_capacity(count);
+    let mut colors = Vec::with_capacity(count);
+    for _ in 0..count {
+        let distance: f64 = rng.gen_range(0.0_f64..1.0).powi(2) * 2.0;
+        let arm = f64::from(rng.gen_range(0..2)) * PI;
+        let angle = arm + 2.5 * distance + rng.gen_range(-0.35..0.35);
+        let spread = 0.08 * (1.0 - 0.5 * distance);
+        positions.push(
+            Point3::new(
+                distance * angle.cos(),
+                rng.gen_range(-1.0..1.0) * spread,
+                distance * angle.sin(),
+            ) + 0.1 * Vec3::random_in_unit_sphere(rng),
+        );
+        radii.push(rng.gen_range(0.004..0.012));
+        colors.push(Vec3::lerp(
+            Color::new(1.0, 0.8, 0.4),
+            Color::new(0.3, 0.5, 1.0),
+            (distance / 2.0).min(1.0),
+        ));
+    }
+    world.add(Arc::new(
+        ParticleSystem::from(
+            StaticTransform::new(Point3::new(0.0, 1.3, 0.0)),
+            positions,
+            radii,
+            Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one())))),
+        )
+        .with_colors(colors),
+    ));
+
+    // A spray of metal droplets, blurred by their velocities
+    let count = 3000;
+    let mut positions = Vec::with_capacity(count);
+    let mut velocities = Vec::with_capacity(count);
+    for _ in 0..count {
+        let direction = Vec3::new(rng.gen_range(-0.3..0.3), 1.0, rng.gen_range(-0.3..0.3));
+        let travelled = rng.gen_range(0.0..1.0);
+        positions.push(direction * travelled);
+        velocities.push(direction * 0.15);
+    }
+    world.add(Arc::new(
+        ParticleSystem::from(
+            StaticTransform::new(Point3::new(2.5, 0.0, -1.5)),
+            positions,
+            vec![0.02; count],
+            Arc::new(Metal::new(Color::new(0.8, 0.85, 0.9), 0.1)),
+        )
+        .with_velocities(velocities, time_range.clone()),
+    ));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(6.0, 4.0, 4.0),
+            look_at: Point3::new(0.5, 1.0, -0.3),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 35.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Dielectric, Lambertian, Metal},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, Cone, Cuboid, Cylinder, Disk, HittableList, LerpTransform,
+    Point3, Quad, Sphere, StaticTransform, Torus, Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let texture = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Quad::from(
+        StaticTransform::new(Point3::zero()),
+        Point3::new(-20.0, 0.0, 20.0),
+        Vec3::new(40.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, -40.0),
+        Arc::new(Lambertian::new(texture)),
+    )));
+
+    let red = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.7, 0.1, 0.1,
+    )))));
+    let blue = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.1, 0.2, 0.7,
+    )))));
+    let gold = Arc::new(Metal::new(Color::new(0.8, 0.6, 0.2), 0.1));
+
+    world.add(Arc::new(Cuboid::from(
+        StaticTransform::new(Point3::new(0.0, 0.75, -3.0)),
+        Vec3::new(1.5, 1.5, 1.5),
+        red.clone(),
+    )));
+    world.add(Arc::new(Cylinder::from(
+        StaticTransform::new(Point3::new(0.0, 1.0, 0.0)),
+        0.75,
+        2.0,
+        true,
+        gold,
+    )));
+    world.add(Arc::new(Cylinder::from(
+        StaticTransform::new(Point3::new(2.0, 0.5, 2.5)),
+        0.5,
+        1.0,
+        false,
+        blue.clone(),
+    )));
+    world.add(Arc::new(Cone::from(
+        StaticTransform::new(Point3::new(0.0, 1.0, 3.0)),
+        0.8,
+        2.0,
+        true,
+        blue,
+    )));
+    world.add(Arc::new(Disk::from(
+        LerpTransform::new(
+            Point3::new(3.0, 2.5, -1.0),
+            Point3::new(3.0, 2.7, -1.0),
+            time_range.clone(),
+        ),
+        0.6,
+        red,
+    )));
+    world.add(Arc::new(Torus::from(
+        StaticTransform::new(Point3::new(0.0, 2.3, 0.0)),
+        1.0,
+        0.3,
+        Arc::new(Metal::new(Color::new(0.8, 0.8, 0.85), 0.05)),
+    )));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(2.5, 0.7, 0.0)),
+        0.7,
+        Arc::new(Dielectric::new(1.5)),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(13.0, 4.0, 3.0),
+            look_at: Point3::new(0.0, 1.0, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 30.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Lambertian, Principled, PrincipledConfig},
+    texture::{Checkered, SolidColor, Texture},
+    BvhNode, CameraConfig, Color, HittableList, Point3, Sphere, StaticTransform, Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+    let uniform =
+        |value: f64| -> Arc<dyn Texture> { Arc::new(SolidColor::new(Color::one() * value)) };
+    let color = |r: f64, g: f64, b: f64| -> Arc<dyn Texture> {
+        Arc::new(SolidColor::new(Color::new(r, g, b)))
+    };
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    // Metallic from front to back against roughness from right to left
+    for row in 0..3 {
+        for column in 0..4 {
+            let config = PrincipledConfig {
+                base_color: color(0.9, 0.5, 0.2),
+                metallic: uniform(row as f64 / 2.0),
+                roughness: uniform(0.05 + 0.3 * column as f64),
+                ..Default::default()
+            };
+            world.add(Arc::new(Sphere::from(
+                StaticTransform::new(Point3::new(
+                    -1.1 * column as f64,
+                    0.45,
+                    1.1 * (row as f64 - 1.0),
+                )),
+                0.45,
+                Arc::new(Principled::new(config)),
+            )));
+        }
+    }
+
+    // A row of the other lobes: car paint, velvet, frosted glass and a lamp
+    let specials = vec![
+        PrincipledConfig {
+            base_color: color(0.6, 0.05, 0.05),
+            roughness: uniform(0.4),
+            clearcoat: uniform(1.0),
+            ..Default::default()
+        },
+        PrincipledConfig {
+            base_color: color(0.2, 0.05, 0.4),
+            roughness: uniform(1.0),
+            specular: uniform(0.0),
+            sheen: uniform(1.0),
+            sheen_tint: uniform(0.8),
+            ..Default::default()
+        },
+        PrincipledConfig {
+            base_color: color(0.8, 0.95, 0.9),
+            roughness: uniform(0.2),
+            transmission: uniform(1.0),
+            ..Default::default()
+        },
+        PrincipledConfig {
+            base_color: color(0.1, 0.1, 0.1),
+            emission: color(1.0, 0.8, 0.5),
+            emission_strength: 3.0,
+            ..Default::default()
+        },
+    ];
+    for (column, config) in specials.into_iter().enumerate() {
+        world.add(Arc::new(Sphere::from(
+            StaticTransform::new(Point3::new(-1.1 * column as f64, 0.45, -2.2)),
+            0.45,
+            Arc::new(Principled::new(config)),
+        )));
+    }
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(5.0, 3.0, 0.5),
+            look_at: Point3::new(-1.6, 0.4, -0.4),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 45.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
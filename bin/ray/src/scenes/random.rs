@@ -44,7 +44,7 @@ pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
                     let mat = Arc::new(Dielectric::new(1.5));
                     Arc::new(Sphere::from(StaticTransform::new(center), 0.2, mat))
                 }
-                16 | 17 | 18 => {
+                16..=18 => {
                     // Metal
                     let albedo = Color::random(rng, 0.5, 1.0);
                     let fuzz = rng.gen_range(0.0..=0.5);
@@ -103,5 +103,6 @@ pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
             aperture: 0.1,
             focus_distance: 10.0,
         },
+        camera_medium: None,
     }
 }
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Lambertian, Metal},
+    sdf::{Repeat, SdfBox, SdfCapsule, SdfSphere, SdfTorus, SmoothUnion, Translate, Twist},
+    texture::{Checkered, SolidColor},
+    Aabb, BvhNode, CameraConfig, Color, HittableList, Point3, SdfObject, Sphere, StaticTransform,
+    Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let texture = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(texture)),
+    )));
+
+    // Organic blob: spheres and a torus melted together
+    let blob = Arc::new(SmoothUnion::new(
+        Arc::new(SmoothUnion::new(
+            Arc::new(SdfSphere::new(0.8)),
+            Arc::new(Translate::new(
+                Vec3::new(0.7, 0.6, 0.0),
+                Arc::new(SdfSphere::new(0.5)),
+            )),
+            0.4,
+        )),
+        Arc::new(Translate::new(
+            Vec3::new(0.0, -0.6, 0.0),
+            Arc::new(SdfTorus::new(1.0, 0.2)),
+        )),
+        0.3,
+    ));
+    let orange = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.9, 0.4, 0.1,
+    )))));
+    world.add(Arc::new(SdfObject::from(
+        StaticTransform::new(Point3::new(0.0, 1.0, 0.0)),
+        blob,
+        Aabb::new(Point3::new(-1.5, -1.0, -1.5), Point3::new(1.5, 1.5, 1.5)),
+        orange,
+    )));
+
+    // Twisted rounded column
+    let column = Arc::new(Twist::new(
+        1.2,
+        0.5,
+        Arc::new(SdfBox::new(Vec3::new(0.6, 2.6, 0.6), 0.05)),
+    ));
+    world.add(Arc::new(SdfObject::from(
+        StaticTransform::new(Point3::new(0.0, 1.3, -3.0)),
+        column,
+        Aabb::new(Point3::new(-0.5, -1.3, -0.5), Point3::new(0.5, 1.3, 0.5)),
+        Arc::new(Metal::new(Color::new(0.8, 0.8, 0.85), 0.1)),
+    )));
+
+    // A grid of pills, repeated in space and cut off by the bounds
+    let pills = Arc::new(Repeat::new(
+        Vec3::new(0.6, 0.0, 0.6),
+        Arc::new(SdfCapsule::new(
+            Point3::new(0.0, -0.2, 0.0),
+            Point3::new(0.0, 0.2, 0.0),
+            0.15,
+        )),
+    ));
+    let blue = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.1, 0.2, 0.7,
+    )))));
+    world.add(Arc::new(SdfObject::from(
+        StaticTransform::new(Point3::new(0.0, 0.35, 3.0)),
+        pills,
+        Aabb::new(Point3::new(-0.9, -0.4, -0.9), Point3::new(0.9, 0.4, 0.9)),
+        blue,
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(7.0, 3.5, 2.0),
+            look_at: Point3::new(0.0, 1.0, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 35.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Dielectric, Lambertian},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, ConstantMedium, HittableList, Point3, Sphere, StaticTransform,
+    Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let texture = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(texture)),
+    )));
+
+    // Dark smoke
+    let boundary = Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(-2.5, 1.5, 0.0)),
+        1.5,
+        Arc::new(Dielectric::new(1.5)),
+    ));
+    world.add(Arc::new(ConstantMedium::new(
+        boundary,
+        0.8,
+        Arc::new(SolidColor::new(Color::zero())),
+    )));
+
+    // Blue subsurface-looking blob: glass shell filled with a dense medium
+    let boundary = Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(2.5, 1.5, 0.0)),
+        1.5,
+        Arc::new(Dielectric::new(1.5)),
+    ));
+    world.add(boundary.clone());
+    world.add(Arc::new(ConstantMedium::new(
+        boundary,
+        2.0,
+        Arc::new(SolidColor::new(Color::new(0.2, 0.4, 0.9))),
+    )));
+
+    // Thin white fog over everything
+    let boundary = Arc::new(Sphere::from(
+        StaticTransform::new(Point3::zero()),
+        50.0,
+        Arc::new(Dielectric::new(1.5)),
+    ));
+    world.add(Arc::new(ConstantMedium::new(
+        boundary,
+        0.01,
+        Arc::new(SolidColor::new(Color::one())),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(13.0, 2.0, 3.0),
+            look_at: Point3::new(0.0, 1.0, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 30.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Lambertian, Metal},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, ControlMesh, HittableList, MeshObject, Point3, Sphere,
+    StaticTransform, SubdivisionScheme, Vec3,
+};
+
+use super::SceneConfig;
+
+/// A cube cage with its top face's edges fully creased and one bottom edge
+/// semi-sharp, in OBJ form as it would come out of a modeling tool.
+const CREASED_CUBE_OBJ: &str = "\
+v -0.8 -0.8 -0.8
+v 0.8 -0.8 -0.8
+v -0.8 0.8 -0.8
+v 0.8 0.8 -0.8
+v -0.8 -0.8 0.8
+v 0.8 -0.8 0.8
+v -0.8 0.8 0.8
+v 0.8 0.8 0.8
+f 1 3 4 2
+f 5 6 8 7
+f 1 2 6 5
+f 3 7 8 4
+f 1 5 7 3
+f 2 4 8 6
+t crease 2/1/0 2 6 10
+t crease 2/1/0 6 7 10
+t crease 2/1/0 7 3 10
+t crease 2/1/0 3 2 10
+t crease 2/1/0 0 1 1.5
+";
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    let cage = ControlMesh::read_obj(CREASED_CUBE_OBJ.as_bytes())
+        .expect("Embedded control mesh should be valid");
+    let clay = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.75, 0.45, 0.35,
+    )))));
+
+    // The unrefined cage, then Catmull-Clark with creases, then Loop
+    let levels = cage.levels_for_screen_size(9.0, 35.0, 400, 2.0);
+    let meshes = [
+        (cage.to_mesh(), -2.2),
+        (
+            cage.subdivided(SubdivisionScheme::CatmullClark, levels)
+                .to_mesh(),
+            0.0,
+        ),
+        (
+            cage.subdivided(SubdivisionScheme::Loop, levels).to_mesh(),
+            2.2,
+        ),
+    ];
+    for (mesh, z) in meshes {
+        world.add(Arc::new(MeshObject::from(
+            rng,
+            StaticTransform::new(Point3::new(0.0, 0.8, z)),
+            Arc::new(mesh),
+            clay.clone(),
+        )));
+    }
+
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(-3.0, 1.2, 0.0)),
+        1.2,
+        Arc::new(Metal::new(Color::new(0.8, 0.8, 0.8), 0.05)),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(8.0, 4.0, 0.0),
+            look_at: Point3::new(0.0, 0.8, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 35.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
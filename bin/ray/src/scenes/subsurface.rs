@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Lambertian, Subsurface},
+    texture::{Checkered, SolidColor},
+    BvhNode, CameraConfig, Color, Cylinder, HittableList, Point3, Sphere, StaticTransform, Torus,
+    Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    let checker = Arc::new(Checkered::new(
+        Arc::new(SolidColor::new(Color::new(0.2, 0.3, 0.1))),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.9))),
+    ));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, -1000.0, 0.0)),
+        1000.0,
+        Arc::new(Lambertian::new(checker)),
+    )));
+
+    // Marble, next to a diffuse ball of the same color to compare against
+    let marble = Color::new(0.83, 0.79, 0.75);
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, 0.5, -1.2)),
+        0.5,
+        Arc::new(Subsurface::new(marble, Color::new(0.08, 0.06, 0.05)).with_roughness(0.1)),
+    )));
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(1.2, 0.5, -1.2)),
+        0.5,
+        Arc::new(Lambertian::new(Arc::new(SolidColor::new(marble)))),
+    )));
+
+    // Skin, with reds travelling furthest and strongly forward scattering
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(1.2, 0.5, 0.0)),
+        0.5,
+        Arc::new(
+            Subsurface::new(Color::new(0.85, 0.6, 0.5), Color::new(0.1, 0.06, 0.04))
+                .with_asymmetry(0.8)
+                .with_roughness(0.4),
+        ),
+    )));
+
+    // A wax candle and a jade ring, both quite translucent
+    world.add(Arc::new(Cylinder::from(
+        StaticTransform::new(Point3::new(0.0, 0.6, 0.0)),
+        0.3,
+        1.2,
+        true,
+        Arc::new(Subsurface::new(
+            Color::new(0.95, 0.85, 0.6),
+            Color::new(0.3, 0.25, 0.15),
+        )),
+    )));
+    world.add(Arc::new(Torus::from(
+        StaticTransform::new(Point3::new(0.6, 0.15, 1.3)),
+        0.45,
+        0.15,
+        Arc::new(
+            Subsurface::new(Color::new(0.3, 0.8, 0.45), Color::new(0.2, 0.5, 0.3))
+                .with_index_of_refraction(1.6)
+                .with_roughness(0.05),
+        ),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(5.0, 1.8, 1.0),
+            look_at: Point3::new(0.5, 0.5, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 40.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
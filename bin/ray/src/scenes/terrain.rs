@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use ray_math::{
+    material::{Dielectric, Lambertian},
+    texture::{Perlin, SolidColor},
+    BvhNode, CameraConfig, Color, Heightfield, HittableList, Point3, Sphere, StaticTransform, Vec3,
+};
+
+use super::SceneConfig;
+
+pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
+    let mut world = HittableList::new();
+    let time_range = 0.0..1.0;
+
+    // Rolling hills from a few octaves of Perlin noise, with ridges where the
+    // noise changes sign
+    let perlin = Perlin::new(rng);
+    let height = |x: f64, z: f64| {
+        let point = Point3::new(x * 0.08, 0.0, z * 0.08);
+        let ridges = 1.0 - perlin.noise(&(point * 2.0)).abs();
+        4.0 * perlin.turbulance(&point, 6) + 1.5 * ridges * ridges
+    };
+    let ground = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::new(
+        0.45, 0.55, 0.3,
+    )))));
+    world.add(Arc::new(Heightfield::from_fn(
+        StaticTransform::new(Point3::zero()),
+        (60.0, 60.0),
+        [512, 512],
+        height,
+        ground,
+    )));
+
+    world.add(Arc::new(Sphere::from(
+        StaticTransform::new(Point3::new(0.0, height(0.0, 0.0) + 2.5, 0.0)),
+        1.5,
+        Arc::new(Dielectric::new(1.5)),
+    )));
+
+    SceneConfig {
+        root: BvhNode::new(rng, world, time_range),
+        camera: CameraConfig {
+            look_from: Point3::new(22.0, 12.0, 18.0),
+            look_at: Point3::new(0.0, 2.0, 0.0),
+            view_up: Vec3::new(0.0, 1.0, 0.0),
+            vertical_field_of_view_degrees: 40.0,
+            aspect_ratio: 3.0 / 2.0,
+            aperture: 0.0,
+            focus_distance: 10.0,
+        },
+        camera_medium: None,
+    }
+}
@@ -36,5 +36,6 @@ pub fn scene(rng: &mut dyn rand::RngCore) -> SceneConfig {
             aperture: 0.0,
             focus_distance: 10.0,
         },
+        camera_medium: None,
     }
 }
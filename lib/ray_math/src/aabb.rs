@@ -1,6 +1,6 @@
-use std::mem;
+use std::{mem, ops::Range};
 
-use crate::{Point3, Ray};
+use crate::{LaneMask, Lanes, Point3, Ray, RayPacket, Transform, Vec3, PACKET_WIDTH};
 
 #[derive(Clone)]
 pub struct Aabb {
@@ -13,7 +13,12 @@ impl Aabb {
         Self { min, max }
     }
 
-    pub fn hit(&self, ray: &Ray, mut t_min: f64, mut t_max: f64) -> bool {
+    pub fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> bool {
+        self.intersect(ray, t_min, t_max).is_some()
+    }
+
+    /// The part of `[t_min, t_max]` for which `ray` is inside the box, if any.
+    pub fn intersect(&self, ray: &Ray, mut t_min: f64, mut t_max: f64) -> Option<(f64, f64)> {
         for a in 0..3 {
             let inv_d = 1.0 / ray.direction()[a];
             let mut t0 = (self.min[a] - ray.origin()[a]) * inv_d;
@@ -24,11 +29,37 @@ impl Aabb {
             t_min = t0.max(t_min);
             t_max = t1.min(t_max);
             if t_max <= t_min {
-                return false;
+                return None;
             }
         }
 
-        true
+        Some((t_min, t_max))
+    }
+
+    /// Slab test for every active ray of `packet` at once. Returns which lanes
+    /// hit the box within their `[t_min, t_max]` interval.
+    pub fn hit_packet(
+        &self,
+        packet: &RayPacket,
+        t_min: f64,
+        t_max: &Lanes,
+        active: &LaneMask,
+    ) -> LaneMask {
+        let splat = |point: Point3| [0, 1, 2].map(|a| [point[a]; PACKET_WIDTH]);
+        let (near, far) = slab_test(
+            &splat(self.min),
+            &splat(self.max),
+            &[0, 1, 2].map(|a| *packet.origin(a)),
+            &[0, 1, 2].map(|a| *packet.inv_direction(a)),
+            t_min,
+            t_max,
+        );
+
+        let mut result = [false; PACKET_WIDTH];
+        for lane in 0..PACKET_WIDTH {
+            result[lane] = active[lane] && near[lane] < far[lane];
+        }
+        result
     }
 
     pub fn min(&self) -> Point3 {
@@ -39,6 +70,55 @@ impl Aabb {
         self.max
     }
 
+    /// The smallest box containing all of `points`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` is empty
+    pub fn from_points(points: &[Point3]) -> Aabb {
+        let mut min = points[0];
+        let mut max = points[0];
+        for point in &points[1..] {
+            for a in 0..3 {
+                min[a] = min[a].min(point[a]);
+                max[a] = max[a].max(point[a]);
+            }
+        }
+        Aabb::new(min, max)
+    }
+
+    /// The box moved by `offset`.
+    pub fn offset(&self, offset: Vec3) -> Aabb {
+        Aabb::new(self.min + offset, self.max + offset)
+    }
+
+    /// The box grown where needed so that no side is thinner than a small
+    /// minimum, since flat boxes (e.g. around a quad) are never hit.
+    pub fn padded(&self) -> Aabb {
+        const MIN_SIZE: f64 = 0.0001;
+        let mut min = self.min;
+        let mut max = self.max;
+        for a in 0..3 {
+            if max[a] - min[a] < MIN_SIZE {
+                min[a] -= MIN_SIZE * 0.5;
+                max[a] += MIN_SIZE * 0.5;
+            }
+        }
+        Aabb::new(min, max)
+    }
+
+    /// Bounds of a shape described by `local` bounds (relative to the shape's
+    /// position) as it moves with `transform` over `time_range`.
+    pub fn swept<T: Transform + ?Sized>(
+        local: &Aabb,
+        transform: &T,
+        time_range: Range<f64>,
+    ) -> Aabb {
+        let box0 = local.offset(transform.position(time_range.start));
+        let box1 = local.offset(transform.position(time_range.end));
+        Aabb::surround(&box0, &box1)
+    }
+
     pub fn surround(box0: &Aabb, box1: &Aabb) -> Aabb {
         let min = Point3::new(
             box0.min.x().min(box1.min.x()),
@@ -55,3 +135,30 @@ impl Aabb {
         Aabb::new(min, max)
     }
 }
+
+/// Slab test of a ray against a box in each lane, where lane `i` tests the ray
+/// from `origin[axis][i]` with inverse direction `inv_direction[axis][i]`
+/// against the box from `min[axis][i]` to `max[axis][i]`. That covers both a
+/// packet of rays against one box and one ray against several boxes. Returns
+/// the distances where each lane enters and leaves its box, which it missed
+/// unless it enters first.
+pub(crate) fn slab_test(
+    min: &[Lanes; 3],
+    max: &[Lanes; 3],
+    origin: &[Lanes; 3],
+    inv_direction: &[Lanes; 3],
+    t_min: f64,
+    t_max: &Lanes,
+) -> (Lanes, Lanes) {
+    let mut near = [t_min; PACKET_WIDTH];
+    let mut far = *t_max;
+    for a in 0..3 {
+        for lane in 0..PACKET_WIDTH {
+            let t0 = (min[a][lane] - origin[a][lane]) * inv_direction[a][lane];
+            let t1 = (max[a][lane] - origin[a][lane]) * inv_direction[a][lane];
+            near[lane] = near[lane].max(t0.min(t1));
+            far[lane] = far[lane].min(t0.max(t1));
+        }
+    }
+    (near, far)
+}
@@ -0,0 +1,282 @@
+use std::{cmp::Ordering, ops::Range, sync::Arc};
+
+use rand::Rng;
+
+use crate::{aabb::slab_test, Aabb, HitResult, Hittable, HittableList, Ray, PACKET_WIDTH};
+
+/// Children per node, as many as the lanes of a slab test.
+const WIDTH: usize = PACKET_WIDTH;
+
+/// A bounding volume hierarchy where each node has up to four children, built
+/// by collapsing two levels of a binary split into one node. The four child
+/// boxes are stored as a structure of arrays so a single ray can be tested
+/// against all of them in one pass.
+pub struct Bvh4 {
+    nodes: Vec<Node>,
+    root: Child,
+    bounds: Aabb,
+}
+
+struct Node {
+    /// `min[axis][child]` and `max[axis][child]` for each of the four children
+    min: [[f64; WIDTH]; 3],
+    max: [[f64; WIDTH]; 3],
+    children: [Child; WIDTH],
+    /// How many children there are, in the first slots. The empty slots'
+    /// inverted boxes can pass the slab test, e.g. for rays going down every
+    /// axis, so only these are visited
+    count: usize,
+}
+
+enum Child {
+    Empty,
+    Node(usize),
+    Leaf(Arc<dyn Hittable>),
+}
+
+impl Bvh4 {
+    pub fn new(
+        rng: &mut dyn rand::RngCore,
+        mut list: HittableList,
+        time_range: Range<f64>,
+    ) -> Self {
+        let items = list.list_mut();
+        assert!(!items.is_empty(), "Cannot construct a Bvh4 with no items");
+
+        let bounds = items
+            .iter()
+            .map(|item| Self::bounds_of(item, time_range.clone()))
+            .reduce(|a, b| Aabb::surround(&a, &b))
+            .unwrap();
+
+        let mut nodes = Vec::new();
+        let root = Self::build(rng, &mut nodes, items, time_range);
+        Self {
+            nodes,
+            root,
+            bounds,
+        }
+    }
+
+    fn build(
+        rng: &mut dyn rand::RngCore,
+        nodes: &mut Vec<Node>,
+        list: &mut [Arc<dyn Hittable>],
+        time_range: Range<f64>,
+    ) -> Child {
+        if list.len() == 1 {
+            return Child::Leaf(Arc::clone(&list[0]));
+        }
+
+        // Split in half, then split each half again: the four groups are the
+        // grandchildren a binary BVH would have produced
+        let groups: Vec<&mut [Arc<dyn Hittable>]> = if list.len() <= WIDTH {
+            list.chunks_mut(1).collect()
+        } else {
+            let mid = Self::split(rng, list, time_range.clone());
+            let (left, right) = list.split_at_mut(mid);
+            let left_mid = Self::split(rng, left, time_range.clone());
+            let right_mid = Self::split(rng, right, time_range.clone());
+            let (a, b) = left.split_at_mut(left_mid);
+            let (c, d) = right.split_at_mut(right_mid);
+            vec![a, b, c, d]
+        };
+
+        let mut node = Node {
+            min: [[f64::INFINITY; WIDTH]; 3],
+            max: [[f64::NEG_INFINITY; WIDTH]; 3],
+            children: [Child::Empty, Child::Empty, Child::Empty, Child::Empty],
+            count: groups.len(),
+        };
+
+        for (index, group) in groups.into_iter().enumerate() {
+            let bounds = group
+                .iter()
+                .map(|item| Self::bounds_of(item, time_range.clone()))
+                .reduce(|a, b| Aabb::surround(&a, &b))
+                .unwrap();
+            for axis in 0..3 {
+                node.min[axis][index] = bounds.min()[axis];
+                node.max[axis][index] = bounds.max()[axis];
+            }
+            node.children[index] = Self::build(rng, nodes, group, time_range.clone());
+        }
+
+        nodes.push(node);
+        Child::Node(nodes.len() - 1)
+    }
+
+    /// Sorts `list` along a random axis and returns the index to split it at.
+    fn split(
+        rng: &mut dyn rand::RngCore,
+        list: &mut [Arc<dyn Hittable>],
+        time_range: Range<f64>,
+    ) -> usize {
+        let axis = rng.gen_range(0..=2);
+        list.sort_by(|a, b| {
+            let box_a = Self::bounds_of(a, time_range.clone());
+            let box_b = Self::bounds_of(b, time_range.clone());
+            box_a.min()[axis]
+                .partial_cmp(&box_b.min()[axis])
+                .unwrap_or(Ordering::Greater)
+        });
+        list.len() / 2
+    }
+
+    fn bounds_of(hittable: &Arc<dyn Hittable>, time_range: Range<f64>) -> Aabb {
+        hittable.bounding_box(time_range).expect(
+            "No bounding box constructing Bvh4 - note: need to implement support for items without a bounding box",
+        )
+    }
+
+    fn hit_child(&self, child: &Child, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        match child {
+            Child::Empty => None,
+            Child::Leaf(hittable) => hittable.hit(ray, t_min, t_max),
+            Child::Node(index) => self.hit_node(&self.nodes[*index], ray, t_min, t_max),
+        }
+    }
+
+    fn hit_node(&self, node: &Node, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        // Slab test against all four child boxes at once
+        let (near, far) = slab_test(
+            &node.min,
+            &node.max,
+            &[0, 1, 2].map(|a| [ray.origin()[a]; WIDTH]),
+            &[0, 1, 2].map(|a| [1.0 / ray.direction()[a]; WIDTH]),
+            t_min,
+            &[t_max; WIDTH],
+        );
+
+        // Visit the children that were hit from nearest to farthest, so that
+        // farther ones can be skipped once something closer is found
+        let mut order = [0, 1, 2, 3];
+        let order = &mut order[..node.count];
+        order.sort_by(|&a, &b| near[a].partial_cmp(&near[b]).unwrap_or(Ordering::Equal));
+
+        let mut closest_so_far = t_max;
+        let mut hit_result = None;
+        for &mut child in order {
+            if near[child] >= far[child] || near[child] >= closest_so_far {
+                continue;
+            }
+
+            if let Some(result) = self.hit_child(&node.children[child], ray, t_min, closest_so_far)
+            {
+                closest_so_far = result.t();
+                hit_result = Some(result);
+            }
+        }
+
+        hit_result
+    }
+}
+
+impl Hittable for Bvh4 {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        match &self.root {
+            Child::Leaf(_) if !self.bounds.hit(ray, t_min, t_max) => None,
+            root => self.hit_child(root, ray, t_min, t_max),
+        }
+    }
+
+    fn bounding_box(&self, _time_range: Range<f64>) -> Option<Aabb> {
+        Some(self.bounds.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{
+        material::Lambertian, texture::SolidColor, BvhNode, Color, Point3, Sphere, StaticTransform,
+        Vec3,
+    };
+
+    #[test]
+    fn hits_match_binary_bvh() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        let (mut list4, mut list2) = (HittableList::new(), HittableList::new());
+        for _ in 0..200 {
+            let center = Vec3::random(&mut rng, -10.0, 10.0);
+            let radius = rng.gen_range(0.1..1.0);
+            let sphere: Arc<dyn Hittable> = Arc::new(Sphere::from(
+                StaticTransform::new(center),
+                radius,
+                Arc::clone(&material) as _,
+            ));
+            list4.add(Arc::clone(&sphere));
+            list2.add(sphere);
+        }
+        let bvh4 = Bvh4::new(&mut rng, list4, 0.0..1.0);
+        let bvh_node = BvhNode::new(&mut rng, list2, 0.0..1.0);
+
+        let mut hits = 0;
+        for _ in 0..2000 {
+            let origin = Point3::random(&mut rng, -15.0, 15.0);
+            let direction = Vec3::random_unit(&mut rng);
+            let ray = Ray::new(origin, direction, 0.0);
+            let expected = bvh_node.hit(&ray, 0.001, f64::INFINITY).map(|hit| hit.t());
+            let actual = bvh4.hit(&ray, 0.001, f64::INFINITY).map(|hit| hit.t());
+            match (expected, actual) {
+                (Some(expected), Some(actual)) => {
+                    assert!(
+                        (expected - actual).abs() < 1e-9,
+                        "{} != {}",
+                        expected,
+                        actual
+                    );
+                    hits += 1;
+                }
+                (None, None) => {}
+                _ => panic!("{:?} != {:?}", expected, actual),
+            }
+        }
+        assert!(hits > 100);
+    }
+
+    #[test]
+    fn partly_filled_nodes() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        for &count in &[2, 3, 7, 13] {
+            let mut list = HittableList::new();
+            for i in 0..count {
+                list.add(Arc::new(Sphere::from(
+                    StaticTransform::new(Point3::new(2.0 * i as f64, 0.0, 0.0)),
+                    0.5,
+                    Arc::clone(&material) as _,
+                )));
+            }
+            let bvh4 = Bvh4::new(&mut rng, list, 0.0..1.0);
+            for node in &bvh4.nodes {
+                assert!((2..=WIDTH).contains(&node.count));
+                for (slot, child) in node.children.iter().enumerate() {
+                    assert_eq!(matches!(child, Child::Empty), slot >= node.count);
+                }
+            }
+
+            // Rays heading down every axis, which also pass the empty slots'
+            // inverted boxes, still only hit the spheres
+            for i in 0..count {
+                let center = Point3::new(2.0 * i as f64, 0.0, 0.0);
+                let ray = Ray::new(
+                    center + Vec3::new(0.2, 3.0, 0.1),
+                    Vec3::new(-0.05, -1.0, -0.02),
+                    0.0,
+                );
+                let hit = bvh4.hit(&ray, 0.001, f64::INFINITY).unwrap();
+                assert!(((hit.point() - center).length() - 0.5).abs() < 1e-9);
+                let miss = Ray::new(
+                    center + Vec3::new(1.0, 3.0, 0.0),
+                    Vec3::new(-1e-3, -1.0, -1e-3),
+                    0.0,
+                );
+                assert!(bvh4.hit(&miss, 0.001, f64::INFINITY).is_none());
+            }
+        }
+    }
+}
@@ -2,7 +2,9 @@ use std::{cmp::Ordering, ops::Range, sync::Arc};
 
 use rand::Rng;
 
-use crate::{Aabb, HitResult, Hittable, HittableList, Ray};
+use crate::{
+    Aabb, HitResult, Hittable, HittableList, LaneMask, Lanes, PacketHitResult, Ray, RayPacket,
+};
 
 pub struct BvhNode {
     left: Arc<dyn Hittable>,
@@ -54,7 +56,7 @@ impl BvhNode {
             }
         };
 
-        const MESSAGE: &'static str = "No bounding box constructing BvhNode - note: need to implement support for items without a bounding box";
+        const MESSAGE: &str = "No bounding box constructing BvhNode - note: need to implement support for items without a bounding box";
         let box_left = left.bounding_box(time_range.clone()).expect(MESSAGE);
         let box_right = right.bounding_box(time_range.clone()).expect(MESSAGE);
 
@@ -71,7 +73,7 @@ impl BvhNode {
         axis: usize,
         time_range: Range<f64>,
     ) -> Ordering {
-        const MESSAGE: &'static str = "No bounding box comparing hittables - note: need to implement support for items without a bounding box";
+        const MESSAGE: &str = "No bounding box comparing hittables - note: need to implement support for items without a bounding box";
         let box_a = a.bounding_box(time_range.clone()).expect(MESSAGE);
         let box_b = b.bounding_box(time_range).expect(MESSAGE);
         box_a.min()[axis]
@@ -98,4 +100,37 @@ impl Hittable for BvhNode {
     fn bounding_box(&self, _time_range: Range<f64>) -> Option<Aabb> {
         Some(self.bounds.clone())
     }
+
+    fn hit_packet(
+        &self,
+        packet: &RayPacket,
+        t_min: f64,
+        t_max: &Lanes,
+        active: &LaneMask,
+    ) -> PacketHitResult {
+        let active = self.bounds.hit_packet(packet, t_min, t_max, active);
+        if !active.iter().any(|&lane| lane) {
+            return PacketHitResult::default();
+        }
+
+        let left_hits = self.left.hit_packet(packet, t_min, t_max, &active);
+
+        // Each lane only needs to look for something closer than what the left
+        // subtree already found
+        let mut closest = *t_max;
+        for (t, hit) in closest.iter_mut().zip(&left_hits) {
+            if let Some(hit) = hit {
+                *t = hit.t();
+            }
+        }
+
+        let right_hits = self.right.hit_packet(packet, t_min, &closest, &active);
+        let mut result = left_hits;
+        for (hit, right_hit) in result.iter_mut().zip(right_hits) {
+            if right_hit.is_some() {
+                *hit = right_hit;
+            }
+        }
+        result
+    }
 }
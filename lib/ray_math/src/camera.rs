@@ -2,7 +2,7 @@ use std::{f64, ops::Range};
 
 use rand::Rng;
 
-use crate::{Point3, Ray, Vec3};
+use crate::{Point3, Ray, RayPacket, Vec3, PACKET_WIDTH};
 
 pub struct Camera {
     origin: Point3,
@@ -77,4 +77,16 @@ impl Camera {
             time_range.map_or(0.0, |range| rng.gen_range(range)),
         )
     }
+
+    /// Builds a packet of defocused rays, one for each `(s, t)` viewport
+    /// coordinate. Nearby coordinates give coherent rays that traverse the
+    /// scene well together.
+    pub fn get_ray_packet_defocused(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        time_range: Option<Range<f64>>,
+        coords: [(f64, f64); PACKET_WIDTH],
+    ) -> RayPacket {
+        RayPacket::new(coords.map(|(s, t)| self.get_ray_defocused(rng, time_range.clone(), s, t)))
+    }
 }
@@ -0,0 +1,159 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    disk::{azimuth_tangent, azimuth_u, azimuth_u_scale, hit_disk},
+    material::Material,
+    solve_quadratic, Aabb, HitResult, Hittable, Point3, Ray, Transform, Vec3,
+};
+
+/// A cone around the Y axis, centered on the transform's position, with its
+/// base of radius `radius` at the bottom and its tip `height` above it. A capped
+/// cone has its base closed by a disk.
+pub struct Cone<T> {
+    transform: T,
+    radius: f64,
+    height: f64,
+    capped: bool,
+    material: Arc<dyn Material>,
+}
+
+impl<T: Transform> Cone<T> {
+    pub fn from(
+        transform: T,
+        radius: f64,
+        height: f64,
+        capped: bool,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            transform,
+            radius,
+            height,
+            capped,
+            material,
+        }
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    pub fn capped(&self) -> bool {
+        self.capped
+    }
+}
+
+impl<T: Transform> Hittable for Cone<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let center = self.transform.position(ray.time());
+        let half_height = 0.5 * self.height;
+        let direction = ray.direction();
+
+        // Work relative to the tip, where the surface is x^2 + z^2 = (k y)^2
+        // for the slope k, with y pointing down into the cone
+        let tip = center + Vec3::new(0.0, half_height, 0.0);
+        let origin = ray.origin() - tip;
+        let k = self.radius / self.height;
+        let k2 = k * k;
+
+        // (t, outward normal, uv, distance per unit of v) of the closest hit
+        // so far
+        let mut closest: Option<(f64, Vec3, (f64, f64), f64)> = None;
+
+        let a = direction.x() * direction.x() + direction.z() * direction.z()
+            - k2 * direction.y() * direction.y();
+        let b = 2.0
+            * (origin.x() * direction.x() + origin.z() * direction.z()
+                - k2 * origin.y() * direction.y());
+        let c = origin.x() * origin.x() + origin.z() * origin.z() - k2 * origin.y() * origin.y();
+        if let Some((t0, t1)) = solve_quadratic(a, b, c) {
+            for t in [t0, t1] {
+                let local = origin + t * direction;
+                // Only the lower nappe, between the tip and the base
+                let depth = -local.y();
+                if t_min <= t && t <= t_max && (0.0..=self.height).contains(&depth) {
+                    let normal = Vec3::new(local.x(), k2 * depth, local.z()).normalized();
+                    let uv = (azimuth_u(&local), 1.0 - depth / self.height);
+                    closest = Some((
+                        t,
+                        normal,
+                        uv,
+                        (self.radius * self.radius + self.height * self.height).sqrt(),
+                    ));
+                    break;
+                }
+            }
+        }
+
+        if self.capped {
+            let t_max = closest.map_or(t_max, |(t, _, _, _)| t);
+            let base_center = center - Vec3::new(0.0, half_height, 0.0);
+            if let Some((t, uv)) = hit_disk(ray, base_center, self.radius, t_min, t_max) {
+                closest = Some((t, Vec3::new(0.0, -1.0, 0.0), uv, self.radius));
+            }
+        }
+
+        let (t, outward_normal, uv, v_scale) = closest?;
+        let point = ray.at(t);
+        Some(
+            HitResult::new(
+                ray,
+                point,
+                outward_normal,
+                t,
+                uv,
+                Arc::clone(&self.material),
+            )
+            .with_tangent(azimuth_tangent(&(point - center)))
+            .with_uv_scale(azimuth_u_scale(&(point - center)), v_scale),
+        )
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        let extent = Point3::new(self.radius, 0.5 * self.height, self.radius);
+        let local = Aabb::new(-extent, extent);
+        Some(Aabb::swept(&local, &self.transform, time_range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{material::Lambertian, texture::SolidColor, Color, StaticTransform};
+
+    #[test]
+    fn side_and_base() {
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        let cone = Cone::from(
+            StaticTransform::new(Point3::zero()),
+            1.0,
+            2.0,
+            true,
+            material,
+        );
+
+        // Halfway up, the cone is half as wide as its base
+        let ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), 0.0);
+        let hit = cone.hit(&ray, 0.001, f64::INFINITY).unwrap();
+        assert!((hit.t() - 4.5).abs() < 1e-9);
+        let expected = Vec3::new(-1.0, 0.5, 0.0).normalized();
+        assert!((hit.geometric_normal() - expected).length() < 1e-9);
+
+        let ray = Ray::new(Point3::new(0.5, -5.0, 0.0), Vec3::new(0.0, 1.0, 0.0), 0.0);
+        let hit = cone.hit(&ray, 0.001, f64::INFINITY).unwrap();
+        assert!((hit.t() - 4.0).abs() < 1e-9);
+        assert!((hit.geometric_normal() - Vec3::new(0.0, -1.0, 0.0)).length() < 1e-9);
+
+        // Past the tip, and above it on the upper nappe
+        let ray = Ray::new(Point3::new(-5.0, 1.5, 0.0), Vec3::new(1.0, 0.0, 0.0), 0.0);
+        assert!(cone.hit(&ray, 0.001, f64::INFINITY).is_none());
+    }
+}
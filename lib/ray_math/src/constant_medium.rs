@@ -0,0 +1,143 @@
+use std::{ops::Range, sync::Arc};
+
+use rand::Rng;
+
+use crate::{
+    material::{Isotropic, Material},
+    texture::Texture,
+    Aabb, HitResult, Hittable, Ray, Vec3,
+};
+
+/// A volume of uniform density filling the inside of `boundary`, such as smoke
+/// or fog. Rays passing through it scatter at a random distance that depends
+/// on the density, so thin media are mostly see-through.
+///
+/// The boundary must be closed and convex, since only the first entry and exit
+/// points along a ray are considered.
+///
+/// `Hittable::hit` isn't given a random number generator, so the scattering
+/// distance is drawn from the thread's own rather than the renderer's.
+pub struct ConstantMedium {
+    boundary: Arc<dyn Hittable>,
+    neg_inv_density: f64,
+    phase_function: Arc<dyn Material>,
+}
+
+impl ConstantMedium {
+    /// # Panics
+    ///
+    /// Panics if `density` isn't positive
+    pub fn new(boundary: Arc<dyn Hittable>, density: f64, albedo: Arc<dyn Texture>) -> Self {
+        Self::with_phase_function(boundary, density, Arc::new(Isotropic::new(albedo)))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `density` isn't positive
+    pub fn with_phase_function(
+        boundary: Arc<dyn Hittable>,
+        density: f64,
+        phase_function: Arc<dyn Material>,
+    ) -> Self {
+        assert!(density > 0.0, "Constant medium density must be positive");
+        Self {
+            boundary,
+            neg_inv_density: -1.0 / density,
+            phase_function,
+        }
+    }
+
+    pub fn boundary(&self) -> &dyn Hittable {
+        &*self.boundary
+    }
+
+    pub fn density(&self) -> f64 {
+        -1.0 / self.neg_inv_density
+    }
+}
+
+impl Hittable for ConstantMedium {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        // Find where the ray enters and leaves the boundary, even if the ray
+        // starts inside it
+        let enter = self.boundary.hit(ray, f64::NEG_INFINITY, f64::INFINITY)?;
+        let exit = self.boundary.hit(ray, enter.t() + 0.0001, f64::INFINITY)?;
+
+        let t_enter = enter.t().max(t_min).max(0.0);
+        let t_exit = exit.t().min(t_max);
+        if t_enter >= t_exit {
+            return None;
+        }
+
+        let ray_length = ray.direction().length();
+        let distance_inside_boundary = (t_exit - t_enter) * ray_length;
+        let random: f64 = rand::thread_rng().gen_range(f64::EPSILON..=1.0);
+        let hit_distance = self.neg_inv_density * random.ln();
+        if hit_distance > distance_inside_boundary {
+            return None;
+        }
+
+        // The normal is arbitrary, since the phase function ignores it
+        let t = t_enter + hit_distance / ray_length;
+        Some(HitResult::new(
+            ray,
+            ray.at(t),
+            Vec3::new(1.0, 0.0, 0.0),
+            t,
+            enter.uv(),
+            Arc::clone(&self.phase_function),
+        ))
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        self.boundary.bounding_box(time_range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{texture::SolidColor, Color, Point3, Sphere, StaticTransform};
+
+    fn medium(density: f64) -> ConstantMedium {
+        let albedo = Arc::new(SolidColor::new(Color::one()));
+        let boundary = Arc::new(Sphere::from(
+            StaticTransform::new(Point3::zero()),
+            1.0,
+            Arc::new(Isotropic::new(albedo.clone())),
+        ));
+        ConstantMedium::new(boundary, density, albedo)
+    }
+
+    #[test]
+    #[should_panic(expected = "density must be positive")]
+    fn zero_density() {
+        medium(0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "density must be positive")]
+    fn negative_density() {
+        medium(-1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "density must be positive")]
+    fn nan_density() {
+        medium(f64::NAN);
+    }
+
+    #[test]
+    fn transmittance_through_the_center() {
+        // Light crosses the diameter of 2 unscattered with probability
+        // exp(-2 density), whatever the length of the ray's direction
+        let medium = medium(0.5);
+        let ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(0.25, 0.0, 0.0), 0.0);
+        let samples = 20_000;
+        let passed = (0..samples)
+            .filter(|_| medium.hit(&ray, 0.0, f64::INFINITY).is_none())
+            .count();
+        let expected = (-1.0f64).exp();
+        assert!((passed as f64 / samples as f64 - expected).abs() < 0.02);
+    }
+}
@@ -0,0 +1,229 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{Aabb, HitResult, Hittable, Point3, Ray};
+
+/// A closed shape whose inside is well defined, so it can be combined with
+/// others using constructive solid geometry.
+pub trait Solid: Hittable {
+    /// Appends every stretch of the infinite line through `ray` that lies
+    /// inside the solid, in order of increasing `t`.
+    fn intervals(&self, ray: &Ray, out: &mut Vec<Interval>);
+}
+
+/// Part of a ray inside a solid, from where it enters to where it leaves.
+#[derive(Clone)]
+pub struct Interval {
+    pub enter: HitResult,
+    pub exit: HitResult,
+}
+
+/// The single interval of a ray through a convex solid, found with two calls
+/// to `hit`, which intersects the ray within the given `t` range.
+pub(crate) fn convex_interval(
+    hit: impl Fn(f64, f64) -> Option<HitResult>,
+    out: &mut Vec<Interval>,
+) {
+    if let Some(enter) = hit(f64::NEG_INFINITY, f64::INFINITY) {
+        if let Some(exit) = hit(enter.t() + 0.0001, f64::INFINITY) {
+            out.push(Interval { enter, exit });
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsgOperation {
+    /// Inside either solid.
+    Union,
+    /// Inside both solids.
+    Intersection,
+    /// Inside the left solid but not the right one.
+    Difference,
+}
+
+/// Two solids combined by a boolean operation. The result is itself a solid,
+/// so combinations can be nested.
+///
+/// Surfaces keep the material of the solid they came from. Surfaces of the
+/// right solid that bound a difference face into it, so their normals are
+/// flipped.
+pub struct Csg {
+    operation: CsgOperation,
+    left: Arc<dyn Solid>,
+    right: Arc<dyn Solid>,
+}
+
+impl Csg {
+    pub fn new(operation: CsgOperation, left: Arc<dyn Solid>, right: Arc<dyn Solid>) -> Self {
+        Self {
+            operation,
+            left,
+            right,
+        }
+    }
+
+    pub fn operation(&self) -> CsgOperation {
+        self.operation
+    }
+
+    fn inside(&self, in_left: bool, in_right: bool) -> bool {
+        match self.operation {
+            CsgOperation::Union => in_left || in_right,
+            CsgOperation::Intersection => in_left && in_right,
+            CsgOperation::Difference => in_left && !in_right,
+        }
+    }
+}
+
+impl Solid for Csg {
+    fn intervals(&self, ray: &Ray, out: &mut Vec<Interval>) {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        self.left.intervals(ray, &mut left);
+        self.right.intervals(ray, &mut right);
+
+        // Every boundary crossing of either solid, in order along the ray
+        struct Event {
+            hit: HitResult,
+            from_left: bool,
+            entering: bool,
+        }
+        let mut events = Vec::with_capacity(2 * (left.len() + right.len()));
+        for (intervals, from_left) in [(left, true), (right, false)] {
+            for interval in intervals {
+                events.push(Event {
+                    hit: interval.enter,
+                    from_left,
+                    entering: true,
+                });
+                events.push(Event {
+                    hit: interval.exit,
+                    from_left,
+                    entering: false,
+                });
+            }
+        }
+        events.sort_by(|a, b| a.hit.t().total_cmp(&b.hit.t()));
+
+        let mut in_left = false;
+        let mut in_right = false;
+        let mut enter: Option<HitResult> = None;
+        for event in events {
+            let was_inside = self.inside(in_left, in_right);
+            if event.from_left {
+                in_left = event.entering;
+            } else {
+                in_right = event.entering;
+            }
+            let is_inside = self.inside(in_left, in_right);
+            if was_inside == is_inside {
+                continue;
+            }
+
+            let hit = if !event.from_left && self.operation == CsgOperation::Difference {
+                event.hit.flipped()
+            } else {
+                event.hit
+            };
+
+            if is_inside {
+                enter = Some(hit);
+            } else if let Some(enter) = enter.take() {
+                out.push(Interval { enter, exit: hit });
+            }
+        }
+    }
+}
+
+impl Hittable for Csg {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let mut intervals = Vec::new();
+        self.intervals(ray, &mut intervals);
+        intervals
+            .into_iter()
+            .flat_map(|interval| [interval.enter, interval.exit])
+            .find(|hit| t_min <= hit.t() && hit.t() <= t_max)
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        let left = self.left.bounding_box(time_range.clone());
+        let right = self.right.bounding_box(time_range);
+        match self.operation {
+            CsgOperation::Union => Some(Aabb::surround(&left?, &right?)),
+            CsgOperation::Intersection => match (left, right) {
+                (Some(left), Some(right)) => {
+                    let mut min = Point3::zero();
+                    let mut max = Point3::zero();
+                    for a in 0..3 {
+                        min[a] = left.min()[a].max(right.min()[a]);
+                        max[a] = left.max()[a].min(right.max()[a]).max(min[a]);
+                    }
+                    Some(Aabb::new(min, max))
+                }
+                (left, right) => left.or(right),
+            },
+            CsgOperation::Difference => left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{material::Lambertian, texture::SolidColor, Color, Sphere, StaticTransform, Vec3};
+
+    /// Two unit spheres overlapping between x = -0.5 and x = 0.5, with the
+    /// intervals of a ray along the X axis through both: where it enters and
+    /// leaves, and whether each hit is on the front face.
+    fn intervals(operation: CsgOperation) -> Vec<(f64, f64, bool, bool)> {
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        let sphere = |x: f64| -> Arc<dyn Solid> {
+            Arc::new(Sphere::from(
+                StaticTransform::new(Point3::new(x, 0.0, 0.0)),
+                1.0,
+                material.clone(),
+            ))
+        };
+        let csg = Csg::new(operation, sphere(-0.5), sphere(0.5));
+        let ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), 0.0);
+        let mut out = Vec::new();
+        csg.intervals(&ray, &mut out);
+        out.iter()
+            .map(|interval| {
+                (
+                    interval.enter.t(),
+                    interval.exit.t(),
+                    interval.enter.front_face(),
+                    interval.exit.front_face(),
+                )
+            })
+            .collect()
+    }
+
+    fn assert_intervals(operation: CsgOperation, expected: &[(f64, f64)]) {
+        let actual = intervals(operation);
+        assert_eq!(actual.len(), expected.len(), "{:?}", operation);
+        for (&(enter, exit, enter_front, exit_front), &(expected_enter, expected_exit)) in
+            actual.iter().zip(expected)
+        {
+            assert!((enter - expected_enter).abs() < 1e-9, "{:?}", operation);
+            assert!((exit - expected_exit).abs() < 1e-9, "{:?}", operation);
+            // Entering the combined solid is always from outside it
+            assert!(enter_front && !exit_front, "{:?}", operation);
+        }
+    }
+
+    #[test]
+    fn union() {
+        assert_intervals(CsgOperation::Union, &[(3.5, 6.5)]);
+    }
+
+    #[test]
+    fn intersection() {
+        assert_intervals(CsgOperation::Intersection, &[(4.5, 5.5)]);
+    }
+
+    #[test]
+    fn difference() {
+        assert_intervals(CsgOperation::Difference, &[(3.5, 4.5)]);
+    }
+}
@@ -0,0 +1,82 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    csg::convex_interval, material::Material, Aabb, HitResult, Hittable, Interval, Point3, Quad,
+    Ray, Solid, Transform, Vec3,
+};
+
+/// A closed axis-aligned box centered on the transform's position, made of six
+/// quads that all follow the same transform.
+pub struct Cuboid<T> {
+    transform: Arc<T>,
+    size: Vec3,
+    sides: [Quad<Arc<T>>; 6],
+}
+
+impl<T: Transform> Cuboid<T> {
+    pub fn from(transform: T, size: Vec3, material: Arc<dyn Material>) -> Self {
+        let transform = Arc::new(transform);
+        let min = -0.5 * size;
+        let max = 0.5 * size;
+        let dx = Vec3::new(size.x(), 0.0, 0.0);
+        let dy = Vec3::new(0.0, size.y(), 0.0);
+        let dz = Vec3::new(0.0, 0.0, size.z());
+
+        // Sides are wound so their normals point outwards
+        let side = |corner: Point3, u: Vec3, v: Vec3| {
+            Quad::from(Arc::clone(&transform), corner, u, v, Arc::clone(&material))
+        };
+        let sides = [
+            side(Point3::new(min.x(), min.y(), max.z()), dx, dy), // front
+            side(Point3::new(max.x(), min.y(), max.z()), -dz, dy), // right
+            side(Point3::new(max.x(), min.y(), min.z()), -dx, dy), // back
+            side(Point3::new(min.x(), min.y(), min.z()), dz, dy), // left
+            side(Point3::new(min.x(), max.y(), max.z()), dx, -dz), // top
+            side(Point3::new(min.x(), min.y(), min.z()), dx, dz), // bottom
+        ];
+
+        Self {
+            transform,
+            size,
+            sides,
+        }
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn size(&self) -> Vec3 {
+        self.size
+    }
+
+    pub fn sides(&self) -> &[Quad<Arc<T>>; 6] {
+        &self.sides
+    }
+}
+
+impl<T: Transform> Hittable for Cuboid<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let mut hit_result: Option<HitResult> = None;
+        let mut closest_so_far = t_max;
+        for side in &self.sides {
+            if let Some(result) = side.hit(ray, t_min, closest_so_far) {
+                closest_so_far = result.t();
+                hit_result = Some(result);
+            }
+        }
+
+        hit_result
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        let local = Aabb::new(-0.5 * self.size, 0.5 * self.size);
+        Some(Aabb::swept(&local, &*self.transform, time_range))
+    }
+}
+
+impl<T: Transform> Solid for Cuboid<T> {
+    fn intervals(&self, ray: &Ray, out: &mut Vec<Interval>) {
+        convex_interval(|t_min, t_max| self.hit(ray, t_min, t_max), out);
+    }
+}
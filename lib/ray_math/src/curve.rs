@@ -0,0 +1,289 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    hittable::perpendicular, material::Material, Aabb, HitResult, Hittable, Point3, Ray, Transform,
+    Vec3,
+};
+
+/// How the cross section of a `Curve` is shaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveShape {
+    /// A flat ribbon that always faces the ray, e.g. for grass blades seen
+    /// from a distance.
+    Flat,
+    /// Still intersected as a ribbon facing the ray, but with the normal bent
+    /// across its width as if it were a round tube, e.g. for hair and fur.
+    Cylinder,
+}
+
+/// A cubic Bézier curve swept out to a width that varies linearly from one
+/// end to the other, relative to the transform's position. It is intersected
+/// directly by recursively splitting the curve, in a space where the ray runs
+/// down the Z axis, into segments short and straight enough to be tested as
+/// line segments.
+///
+/// Hits have `u` along the curve and `v` across it, and a shading tangent
+/// along the curve, which is what `Hair` scatters around.
+pub struct Curve<T> {
+    transform: T,
+    control_points: [Point3; 4],
+    widths: (f64, f64),
+    shape: CurveShape,
+    material: Arc<dyn Material>,
+}
+
+/// The closest hit found so far while splitting the curve.
+struct CurveHit {
+    z: f64,
+    u: f64,
+    width: f64,
+}
+
+impl<T: Transform> Curve<T> {
+    /// `widths` are the curve's width at its first and last control point.
+    pub fn from(
+        transform: T,
+        control_points: [Point3; 4],
+        widths: (f64, f64),
+        shape: CurveShape,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            transform,
+            control_points,
+            widths,
+            shape,
+            material,
+        }
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn control_points(&self) -> &[Point3; 4] {
+        &self.control_points
+    }
+
+    fn max_half_width(&self) -> f64 {
+        0.5 * self.widths.0.max(self.widths.1)
+    }
+
+    /// Checks the part of the curve between `u0` and `u1`, with control
+    /// points `points` in ray space, keeping the hit closest to the ray origin
+    /// in `closest`.
+    fn hit_segment(
+        &self,
+        points: &[Point3; 4],
+        (u0, u1): (f64, f64),
+        depth: u32,
+        z_range: (f64, f64),
+        closest: &mut Option<CurveHit>,
+    ) {
+        let z_max = closest.as_ref().map_or(z_range.1, |hit| hit.z);
+        let half_width = self.max_half_width();
+        let bounds = Aabb::from_points(points);
+        if bounds.min().x() - half_width > 0.0
+            || bounds.max().x() + half_width < 0.0
+            || bounds.min().y() - half_width > 0.0
+            || bounds.max().y() + half_width < 0.0
+            || bounds.min().z() - half_width > z_max
+            || bounds.max().z() + half_width < z_range.0
+        {
+            return;
+        }
+
+        if depth > 0 {
+            let (first, second) = split_bezier(points);
+            let u_mid = 0.5 * (u0 + u1);
+            self.hit_segment(&first, (u0, u_mid), depth - 1, z_range, closest);
+            self.hit_segment(&second, (u_mid, u1), depth - 1, z_range, closest);
+            return;
+        }
+
+        // The ray (the Z axis) must pass between the lines perpendicular to
+        // the segment at each of its ends
+        let [p0, p1, p2, p3] = *points;
+        if (p1.y() - p0.y()) * -p0.y() + p0.x() * (p0.x() - p1.x()) < 0.0
+            || (p2.y() - p3.y()) * -p3.y() + p3.x() * (p3.x() - p2.x()) < 0.0
+        {
+            return;
+        }
+
+        // Closest point to the ray along the segment's chord
+        let (sx, sy) = (p3.x() - p0.x(), p3.y() - p0.y());
+        let length_squared = sx * sx + sy * sy;
+        if length_squared == 0.0 {
+            return;
+        }
+        let w = ((-p0.x() * sx - p0.y() * sy) / length_squared).clamp(0.0, 1.0);
+        let point = eval_bezier(points, w);
+        let u = u0 + w * (u1 - u0);
+        let width = self.widths.0 + u * (self.widths.1 - self.widths.0);
+        if point.x() * point.x() + point.y() * point.y() > 0.25 * width * width
+            || point.z() < z_range.0
+            || point.z() > z_max
+        {
+            return;
+        }
+
+        *closest = Some(CurveHit {
+            z: point.z(),
+            u,
+            width,
+        });
+    }
+}
+
+impl<T: Transform> Hittable for Curve<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let position = self.transform.position(ray.time());
+        let ray_length = ray.direction().length();
+        let forward = ray.direction() / ray_length;
+        let right = perpendicular(&forward);
+        let up = forward.cross(&right);
+
+        // Control points relative to the ray: Z is distance along it
+        let to_ray_space = |point: &Point3| {
+            let offset = *point + position - ray.origin();
+            Point3::new(offset.dot(&right), offset.dot(&up), offset.dot(&forward))
+        };
+        let points = self.control_points.map(|point| to_ray_space(&point));
+
+        // Split until each segment is within a small fraction of the width
+        // of straight
+        let mut curvature: f64 = 0.0;
+        for i in 0..2 {
+            let second_difference = points[i] - 2.0 * points[i + 1] + points[i + 2];
+            for axis in 0..3 {
+                curvature = curvature.max(second_difference[axis].abs());
+            }
+        }
+        let epsilon = 0.1 * self.max_half_width();
+        let depth = if curvature > 0.0 && epsilon > 0.0 {
+            ((std::f64::consts::SQRT_2 * 6.0 * curvature / (8.0 * epsilon)).log2() / 2.0)
+                .clamp(0.0, 10.0) as u32
+        } else {
+            0
+        };
+
+        let mut closest = None;
+        let z_range = (t_min * ray_length, t_max.min(f64::MAX) * ray_length);
+        self.hit_segment(&points, (0.0, 1.0), depth, z_range, &mut closest);
+        let CurveHit { z, u, width } = closest?;
+
+        // The frame around the curve at the hit: along it, towards the
+        // viewer, and across it
+        let t = z / ray_length;
+        let point = ray.at(t);
+        let center = eval_bezier(&self.control_points, u) + position;
+        let derivative = bezier_derivative(&self.control_points, u);
+        let tangent = if derivative.nearly_zero() {
+            self.control_points[3] - self.control_points[0]
+        } else {
+            derivative
+        }
+        .normalized();
+        let toward_viewer = -forward - (-forward).dot(&tangent) * tangent;
+        let toward_viewer = if toward_viewer.nearly_zero() {
+            perpendicular(&tangent)
+        } else {
+            toward_viewer.normalized()
+        };
+        let across = tangent.cross(&toward_viewer);
+
+        // Signed distance across the curve, from -1 to 1
+        let h = ((point - center).dot(&across) / (0.5 * width)).clamp(-1.0, 1.0);
+        let shading_normal = match self.shape {
+            CurveShape::Flat => toward_viewer,
+            CurveShape::Cylinder => h * across + (1.0 - h * h).sqrt() * toward_viewer,
+        };
+
+        Some(
+            HitResult::new(
+                ray,
+                point,
+                toward_viewer,
+                t,
+                (u, 0.5 * (h + 1.0)),
+                Arc::clone(&self.material),
+            )
+            .with_shading_normal(shading_normal)
+            .with_tangent(tangent)
+            .with_uv_scale(derivative.length(), width),
+        )
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        // The control points' hull contains the curve
+        let bounds = Aabb::from_points(&self.control_points);
+        let half_width = Vec3::one() * self.max_half_width();
+        let local = Aabb::new(bounds.min() - half_width, bounds.max() + half_width);
+        Some(Aabb::swept(&local, &self.transform, time_range))
+    }
+}
+
+fn eval_bezier(points: &[Point3; 4], u: f64) -> Point3 {
+    let v = 1.0 - u;
+    v * v * v * points[0]
+        + 3.0 * v * v * u * points[1]
+        + 3.0 * v * u * u * points[2]
+        + u * u * u * points[3]
+}
+
+fn bezier_derivative(points: &[Point3; 4], u: f64) -> Vec3 {
+    let v = 1.0 - u;
+    3.0 * (v * v * (points[1] - points[0])
+        + 2.0 * v * u * (points[2] - points[1])
+        + u * u * (points[3] - points[2]))
+}
+
+/// Splits a cubic Bézier curve in half with de Casteljau's algorithm.
+fn split_bezier(points: &[Point3; 4]) -> ([Point3; 4], [Point3; 4]) {
+    let mid = |a: Point3, b: Point3| 0.5 * (a + b);
+    let (p01, p12, p23) = (
+        mid(points[0], points[1]),
+        mid(points[1], points[2]),
+        mid(points[2], points[3]),
+    );
+    let (p012, p123) = (mid(p01, p12), mid(p12, p23));
+    let center = mid(p012, p123);
+    (
+        [points[0], p01, p012, center],
+        [center, p123, p23, points[3]],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{material::Lambertian, texture::SolidColor, Color, StaticTransform};
+
+    #[test]
+    fn straight_ribbon() {
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        let curve = Curve::from(
+            StaticTransform::new(Point3::zero()),
+            [-1.0, -1.0 / 3.0, 1.0 / 3.0, 1.0].map(|x| Point3::new(x, 0.0, 0.0)),
+            (0.2, 0.2),
+            CurveShape::Flat,
+            material,
+        );
+        let down =
+            |x: f64, y: f64| Ray::new(Point3::new(x, y, 5.0), Vec3::new(0.0, 0.0, -2.0), 0.0);
+
+        let hit = curve.hit(&down(0.5, 0.05), 0.0, f64::INFINITY).unwrap();
+        assert!((hit.t() - 2.5).abs() < 1e-9);
+        assert!((hit.normal() - Vec3::new(0.0, 0.0, 1.0)).length() < 1e-9);
+        let (u, v) = hit.uv();
+        assert!((u - 0.75).abs() < 1e-6);
+        // Three quarters of the way across, in one direction or the other
+        assert!((v - 0.5).abs() - 0.25 < 1e-6);
+        let (u_scale, v_scale) = hit.uv_scale();
+        assert!((u_scale - 2.0).abs() < 1e-9 && (v_scale - 0.2).abs() < 1e-9);
+
+        // Beyond the half width, and past the end
+        assert!(curve.hit(&down(0.5, 0.15), 0.0, f64::INFINITY).is_none());
+        assert!(curve.hit(&down(1.2, 0.0), 0.0, f64::INFINITY).is_none());
+    }
+}
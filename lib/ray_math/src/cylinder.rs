@@ -0,0 +1,192 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    csg::convex_interval,
+    disk::{azimuth_tangent, azimuth_u, azimuth_u_scale, hit_disk},
+    material::Material,
+    solve_quadratic, Aabb, HitResult, Hittable, Interval, Point3, Ray, Solid, Transform, Vec3,
+};
+
+/// A cylinder around the Y axis, centered on the transform's position. An
+/// uncapped cylinder is an open tube that can be seen into, but is still
+/// treated as closed when used as a `Solid`.
+pub struct Cylinder<T> {
+    transform: T,
+    radius: f64,
+    height: f64,
+    capped: bool,
+    material: Arc<dyn Material>,
+}
+
+impl<T: Transform> Cylinder<T> {
+    pub fn from(
+        transform: T,
+        radius: f64,
+        height: f64,
+        capped: bool,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            transform,
+            radius,
+            height,
+            capped,
+            material,
+        }
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    pub fn capped(&self) -> bool {
+        self.capped
+    }
+
+    fn hit_surface(&self, ray: &Ray, t_min: f64, t_max: f64, capped: bool) -> Option<HitResult> {
+        let center = self.transform.position(ray.time());
+        let half_height = 0.5 * self.height;
+        let origin = ray.origin() - center;
+        let direction = ray.direction();
+
+        // (t, outward normal, uv, distance per unit of v) of the closest hit
+        // so far
+        let mut closest: Option<(f64, Vec3, (f64, f64), f64)> = None;
+
+        let a = direction.x() * direction.x() + direction.z() * direction.z();
+        let b = 2.0 * (origin.x() * direction.x() + origin.z() * direction.z());
+        let c = origin.x() * origin.x() + origin.z() * origin.z() - self.radius * self.radius;
+        if let Some((t0, t1)) = solve_quadratic(a, b, c) {
+            for t in [t0, t1] {
+                let local = origin + t * direction;
+                if t_min <= t && t <= t_max && local.y().abs() <= half_height {
+                    let normal = Vec3::new(local.x(), 0.0, local.z()) / self.radius;
+                    let uv = (azimuth_u(&local), (local.y() + half_height) / self.height);
+                    closest = Some((t, normal, uv, self.height));
+                    break;
+                }
+            }
+        }
+
+        if capped {
+            for side in [-1.0, 1.0] {
+                let t_max = closest.map_or(t_max, |(t, _, _, _)| t);
+                let cap_center = center + Vec3::new(0.0, side * half_height, 0.0);
+                if let Some((t, uv)) = hit_disk(ray, cap_center, self.radius, t_min, t_max) {
+                    closest = Some((t, Vec3::new(0.0, side, 0.0), uv, self.radius));
+                }
+            }
+        }
+
+        let (t, outward_normal, uv, v_scale) = closest?;
+        let point = ray.at(t);
+        Some(
+            HitResult::new(
+                ray,
+                point,
+                outward_normal,
+                t,
+                uv,
+                Arc::clone(&self.material),
+            )
+            .with_tangent(azimuth_tangent(&(point - center)))
+            .with_uv_scale(azimuth_u_scale(&(point - center)), v_scale),
+        )
+    }
+}
+
+impl<T: Transform> Hittable for Cylinder<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        self.hit_surface(ray, t_min, t_max, self.capped)
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        let extent = Point3::new(self.radius, 0.5 * self.height, self.radius);
+        let local = Aabb::new(-extent, extent);
+        Some(Aabb::swept(&local, &self.transform, time_range))
+    }
+}
+
+impl<T: Transform> Solid for Cylinder<T> {
+    fn intervals(&self, ray: &Ray, out: &mut Vec<Interval>) {
+        convex_interval(
+            |t_min, t_max| self.hit_surface(ray, t_min, t_max, true),
+            out,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{material::Lambertian, texture::SolidColor, Color, StaticTransform};
+
+    fn cylinder(capped: bool) -> Cylinder<StaticTransform> {
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        Cylinder::from(
+            StaticTransform::new(Point3::zero()),
+            1.0,
+            2.0,
+            capped,
+            material,
+        )
+    }
+
+    fn hit(
+        cylinder: &Cylinder<StaticTransform>,
+        origin: Point3,
+        direction: Vec3,
+    ) -> Option<(f64, Vec3)> {
+        let ray = Ray::new(origin, direction, 0.0);
+        cylinder
+            .hit(&ray, 0.001, f64::INFINITY)
+            .map(|hit| (hit.t(), hit.geometric_normal()))
+    }
+
+    #[test]
+    fn side_and_caps() {
+        let capped = cylinder(true);
+        let (t, normal) = hit(
+            &capped,
+            Point3::new(-5.0, 0.5, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        )
+        .unwrap();
+        assert!((t - 4.0).abs() < 1e-9);
+        assert!((normal - Vec3::new(-1.0, 0.0, 0.0)).length() < 1e-9);
+
+        let (t, normal) = hit(
+            &capped,
+            Point3::new(0.5, 5.0, 0.0),
+            Vec3::new(0.0, -1.0, 0.0),
+        )
+        .unwrap();
+        assert!((t - 4.0).abs() < 1e-9);
+        assert!((normal - Vec3::new(0.0, 1.0, 0.0)).length() < 1e-9);
+
+        // Down an open tube without touching its wall
+        let open = cylinder(false);
+        assert!(hit(&open, Point3::new(0.5, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).is_none());
+        // From inside, the far wall
+        let (t, _) = hit(&open, Point3::zero(), Vec3::new(0.0, 0.0, 1.0)).unwrap();
+        assert!((t - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn open_tubes_are_closed_solids() {
+        let ray = Ray::new(Point3::new(0.5, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0);
+        let mut intervals = Vec::new();
+        cylinder(false).intervals(&ray, &mut intervals);
+        assert_eq!(intervals.len(), 1);
+        assert!((intervals[0].enter.t() - 4.0).abs() < 1e-9);
+        assert!((intervals[0].exit.t() - 6.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,98 @@
+use std::{f64, ops::Range, sync::Arc};
+
+use crate::{material::Material, Aabb, HitResult, Hittable, Point3, Ray, Transform, Vec3};
+
+/// A flat circle centered on the transform's position, facing up the Y axis.
+pub struct Disk<T> {
+    transform: T,
+    radius: f64,
+    material: Arc<dyn Material>,
+}
+
+impl<T: Transform> Disk<T> {
+    pub fn from(transform: T, radius: f64, material: Arc<dyn Material>) -> Self {
+        Self {
+            transform,
+            radius,
+            material,
+        }
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+impl<T: Transform> Hittable for Disk<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let center = self.transform.position(ray.time());
+        let (t, uv) = hit_disk(ray, center, self.radius, t_min, t_max)?;
+        let point = ray.at(t);
+        Some(
+            HitResult::new(
+                ray,
+                point,
+                Vec3::new(0.0, 1.0, 0.0),
+                t,
+                uv,
+                Arc::clone(&self.material),
+            )
+            .with_tangent(azimuth_tangent(&(point - center)))
+            .with_uv_scale(azimuth_u_scale(&(point - center)), self.radius),
+        )
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        let extent = Vec3::new(self.radius, 0.0, self.radius);
+        let local = Aabb::new(-extent, extent).padded();
+        Some(Aabb::swept(&local, &self.transform, time_range))
+    }
+}
+
+/// Intersects `ray` with a Y-facing disk at `center`, returning `t` and the
+/// disk's UV coordinates: `u` is the angle around the Y axis and `v` the
+/// distance from the center, both in [0,1].
+pub(crate) fn hit_disk(
+    ray: &Ray,
+    center: Point3,
+    radius: f64,
+    t_min: f64,
+    t_max: f64,
+) -> Option<(f64, (f64, f64))> {
+    if ray.direction().y().abs() < 1e-8 {
+        return None;
+    }
+
+    let t = (center.y() - ray.origin().y()) / ray.direction().y();
+    if t < t_min || t_max < t {
+        return None;
+    }
+
+    let local = ray.at(t) - center;
+    let distance_squared = local.x() * local.x() + local.z() * local.z();
+    if distance_squared > radius * radius {
+        return None;
+    }
+
+    Some((t, (azimuth_u(&local), distance_squared.sqrt() / radius)))
+}
+
+/// Direction of increasing `azimuth_u` at `local`.
+pub(crate) fn azimuth_tangent(local: &Point3) -> Vec3 {
+    Vec3::new(local.z(), 0.0, -local.x())
+}
+
+/// Distance moved around the Y axis at `local` per unit of `azimuth_u`.
+pub(crate) fn azimuth_u_scale(local: &Point3) -> f64 {
+    2.0 * f64::consts::PI * azimuth_tangent(local).length()
+}
+
+/// Angle of `local` around the Y axis, mapped to [0,1] the same way as
+/// `Sphere`'s `u` coordinate.
+pub(crate) fn azimuth_u(local: &Point3) -> f64 {
+    ((-local.z()).atan2(local.x()) + f64::consts::PI) * 0.5 * f64::consts::FRAC_1_PI
+}
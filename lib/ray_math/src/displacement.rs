@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use crate::{texture::Texture, Mesh, Point3, Vec3};
+
+impl Mesh {
+    /// Splits triangles until no edge is longer than `max_edge_length`. Each
+    /// pass splits every long edge at its midpoint and fills each triangle in
+    /// with smaller ones according to how many of its edges were split. Edges
+    /// are split or not by their length alone, so neighbouring triangles
+    /// always agree and no cracks open up between them. Normals and UVs are
+    /// interpolated onto the new vertices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_edge_length` is not positive
+    pub fn tessellated(&self, max_edge_length: f64) -> Mesh {
+        assert!(
+            max_edge_length > 0.0,
+            "Tessellation edge length must be positive"
+        );
+
+        let mut vertices = Vertices {
+            positions: self.positions().to_vec(),
+            normals: self.normals().to_vec(),
+            uvs: self.uvs().to_vec(),
+        };
+        let mut triangles = self.triangles().to_vec();
+        loop {
+            let mut midpoints = HashMap::new();
+            let mut next = Vec::with_capacity(triangles.len());
+            for &triangle in &triangles {
+                let splits = [0, 1, 2].map(|edge| {
+                    let (i, j) = (triangle[edge], triangle[(edge + 1) % 3]);
+                    let length = (vertices.positions[i] - vertices.positions[j]).length();
+                    if length > max_edge_length {
+                        let key = (i.min(j), i.max(j));
+                        Some(
+                            *midpoints
+                                .entry(key)
+                                .or_insert_with(|| vertices.add_midpoint(i, j)),
+                        )
+                    } else {
+                        None
+                    }
+                });
+                split_triangle(triangle, splits, &mut next);
+            }
+
+            let done = midpoints.is_empty();
+            triangles = next;
+            if done {
+                break;
+            }
+        }
+
+        let mesh = Mesh::new(vertices.positions, triangles);
+        let mesh = if vertices.normals.is_empty() {
+            mesh
+        } else {
+            mesh.with_normals(vertices.normals)
+        };
+        if vertices.uvs.is_empty() {
+            mesh
+        } else {
+            mesh.with_uvs(vertices.uvs)
+        }
+    }
+
+    /// Moves every vertex along its normal by the brightness (mean of the
+    /// color channels) of `height` times `scale`, then recomputes smooth
+    /// normals for the new shape. Tessellate first so there are enough
+    /// vertices to carry the detail. A mesh without normals is displaced along
+    /// its smooth normals.
+    ///
+    /// Vertices that are duplicated to give different UVs on either side of a
+    /// seam are only displaced by the same amount if `height` agrees there,
+    /// which point-based (procedural) textures always do.
+    pub fn displaced(&self, height: &dyn Texture, scale: f64) -> Mesh {
+        let smooth;
+        let source = if self.normals().is_empty() {
+            smooth = Mesh::new(self.positions().to_vec(), self.triangles().to_vec())
+                .with_smooth_normals();
+            &smooth
+        } else {
+            self
+        };
+
+        let positions = source
+            .positions()
+            .iter()
+            .zip(source.normals())
+            .enumerate()
+            .map(|(index, (position, normal))| {
+                let uv = self.uvs().get(index).copied().unwrap_or((0.0, 0.0));
+                let color = height.value(uv, position);
+                let offset = (color.x() + color.y() + color.z()) / 3.0 * scale;
+                *position + offset * *normal
+            })
+            .collect();
+
+        let mesh = Mesh::new(positions, self.triangles().to_vec());
+        let mesh = if self.uvs().is_empty() {
+            mesh
+        } else {
+            mesh.with_uvs(self.uvs().to_vec())
+        };
+        mesh.with_smooth_normals()
+    }
+}
+
+/// Vertex attributes of a mesh being tessellated. Normals and UVs are either
+/// empty or hold one entry per position.
+struct Vertices {
+    positions: Vec<Point3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<(f64, f64)>,
+}
+
+impl Vertices {
+    fn add_midpoint(&mut self, i: usize, j: usize) -> usize {
+        self.positions
+            .push(0.5 * (self.positions[i] + self.positions[j]));
+        if !self.normals.is_empty() {
+            let normal = self.normals[i] + self.normals[j];
+            self.normals.push(if normal.nearly_zero() {
+                self.normals[i]
+            } else {
+                normal.normalized()
+            });
+        }
+        if !self.uvs.is_empty() {
+            let (a, b) = (self.uvs[i], self.uvs[j]);
+            self.uvs.push((0.5 * (a.0 + b.0), 0.5 * (a.1 + b.1)));
+        }
+        self.positions.len() - 1
+    }
+}
+
+/// Fills `triangle` in with smaller triangles given the midpoints of its split
+/// edges, where edge `k` runs from vertex `k` to the next. Winding is kept.
+fn split_triangle(triangle: [usize; 3], splits: [Option<usize>; 3], out: &mut Vec<[usize; 3]>) {
+    let count = splits.iter().flatten().count();
+    if count == 0 {
+        out.push(triangle);
+        return;
+    }
+    if count == 3 {
+        let [a, b, c] = triangle;
+        let [ab, bc, ca] = splits.map(Option::unwrap);
+        out.extend([[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]);
+        return;
+    }
+
+    // Rotate so that edge 0 is split, and for two splits edge 1 is too
+    let rotation = (0..3)
+        .find(|&r| splits[r].is_some() && (count == 1 || splits[(r + 1) % 3].is_some()))
+        .unwrap();
+    let [a, b, c] = [0, 1, 2].map(|k| triangle[(k + rotation) % 3]);
+    let [ab, bc, _] = [0, 1, 2].map(|k| splits[(k + rotation) % 3]);
+    let ab = ab.unwrap();
+    match bc {
+        None => out.extend([[a, ab, c], [ab, b, c]]),
+        Some(bc) => out.extend([[ab, b, bc], [a, ab, bc], [a, bc, c]]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tessellation_bounds_edges_without_cracks() {
+        // A square and a long thin triangle sharing one of its edges, so
+        // triangles with one, two and three long edges all get split
+        let mesh = Mesh::new(
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(1.0, 1.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+                Point3::new(3.0, 0.5, 0.0),
+            ],
+            vec![[0, 1, 2], [0, 2, 3], [1, 4, 2]],
+        );
+        let max_edge_length = 0.3;
+        let tessellated = mesh.tessellated(max_edge_length);
+
+        let positions = tessellated.positions();
+        let area = |mesh: &Mesh| -> Vec3 {
+            mesh.triangles()
+                .iter()
+                .map(|&[a, b, c]| {
+                    let p = mesh.positions();
+                    0.5 * (p[b] - p[a]).cross(&(p[c] - p[a]))
+                })
+                .fold(Vec3::zero(), |a, b| a + b)
+        };
+        // Same area, with every triangle still facing the same way
+        assert!((area(&tessellated) - area(&mesh)).length() < 1e-9);
+        assert!(tessellated.triangles().iter().all(|&[a, b, c]| {
+            (positions[b] - positions[a])
+                .cross(&(positions[c] - positions[a]))
+                .z()
+                > 0.0
+        }));
+
+        let mut edges = HashMap::new();
+        for triangle in tessellated.triangles() {
+            for edge in 0..3 {
+                let (i, j) = (triangle[edge], triangle[(edge + 1) % 3]);
+                assert!((positions[i] - positions[j]).length() <= max_edge_length);
+                *edges.entry((i.min(j), i.max(j))).or_insert(0) += 1;
+            }
+        }
+        // Edges used by only one triangle make up the original outline, so
+        // none are left open inside the mesh
+        let outline: f64 = edges
+            .iter()
+            .filter(|(_, &count)| count == 1)
+            .map(|(&(i, j), _)| (positions[i] - positions[j]).length())
+            .sum();
+        let expected = 3.0 + 2.0 * (4.0f64 + 0.25).sqrt();
+        assert!((outline - expected).abs() < 1e-9, "{}", outline);
+    }
+}
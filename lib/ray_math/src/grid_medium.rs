@@ -0,0 +1,320 @@
+use std::{ops::Range, sync::Arc};
+
+use rand::Rng;
+
+use crate::{
+    material::{Material, ScatterResult},
+    Aabb, Color, HitResult, Hittable, Point3, Ray, Vec3, VoxelGrid,
+};
+
+/// Number of density voxels along each axis covered by one majorant cell.
+const MAJORANT_CELL_SIZE: usize = 8;
+
+pub enum VolumeEmission {
+    None,
+    /// Emitted radiance is the grid value multiplied by `color`.
+    Grid {
+        values: Arc<VoxelGrid>,
+        color: Color,
+    },
+    /// Grid values are temperatures in Kelvin, emitting as a black body scaled
+    /// by `intensity`.
+    Temperature {
+        kelvin: Arc<VoxelGrid>,
+        intensity: f64,
+    },
+}
+
+pub struct GridMediumConfig {
+    /// World-space box the voxel grids are stretched over.
+    pub bounds: Aabb,
+    pub density: Arc<VoxelGrid>,
+    /// Multiplier turning grid values into extinction per unit distance.
+    pub density_scale: f64,
+    pub albedo: Color,
+    pub emission: VolumeEmission,
+}
+
+/// A participating medium whose density varies through space according to a
+/// voxel grid. Collisions are found with delta tracking against a coarse grid
+/// of majorant (upper bound) densities, so empty and thin regions are skipped
+/// in a few large steps.
+pub struct GridMedium {
+    volume: Arc<Volume>,
+    phase_function: Arc<dyn Material>,
+}
+
+struct Volume {
+    config: GridMediumConfig,
+    majorant: VoxelGrid,
+}
+
+struct VolumePhase {
+    volume: Arc<Volume>,
+}
+
+impl GridMedium {
+    pub fn new(config: GridMediumConfig) -> Self {
+        let volume = Arc::new(Volume {
+            majorant: Self::build_majorant(&config.density),
+            config,
+        });
+
+        Self {
+            phase_function: Arc::new(VolumePhase {
+                volume: Arc::clone(&volume),
+            }),
+            volume,
+        }
+    }
+
+    pub fn config(&self) -> &GridMediumConfig {
+        &self.volume.config
+    }
+
+    /// Estimates the fraction of light that makes it along `ray` between
+    /// `t_min` and `t_max` using ratio tracking, e.g. for shadow rays.
+    pub fn transmittance(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        ray: &Ray,
+        t_min: f64,
+        t_max: f64,
+    ) -> f64 {
+        let mut transmittance = 1.0;
+        self.volume.track(rng, ray, t_min, t_max, |_, _, ratio| {
+            transmittance *= 1.0 - ratio.min(1.0);
+            transmittance <= 0.0
+        });
+        transmittance
+    }
+
+    /// Maximum density over each block of `MAJORANT_CELL_SIZE` voxels,
+    /// including the neighbouring voxels that trilinear interpolation reads.
+    fn build_majorant(density: &VoxelGrid) -> VoxelGrid {
+        let resolution = density.resolution();
+        let cells = resolution.map(|n| n.div_ceil(MAJORANT_CELL_SIZE));
+
+        let mut values = Vec::with_capacity(cells[0] * cells[1] * cells[2]);
+        for ck in 0..cells[2] {
+            for cj in 0..cells[1] {
+                for ci in 0..cells[0] {
+                    let range = |c: usize| {
+                        let start = (c * MAJORANT_CELL_SIZE) as isize - 1;
+                        start..=start + MAJORANT_CELL_SIZE as isize + 1
+                    };
+
+                    let mut max: f64 = 0.0;
+                    for k in range(ck) {
+                        for j in range(cj) {
+                            for i in range(ci) {
+                                max = max.max(density.voxel(i, j, k));
+                            }
+                        }
+                    }
+                    values.push(max as f32);
+                }
+            }
+        }
+
+        VoxelGrid::new(cells, values)
+    }
+}
+
+impl Volume {
+    fn to_local(&self, point: &Point3) -> Point3 {
+        let min = self.config.bounds.min();
+        let size = self.config.bounds.max() - min;
+        Point3::new(
+            (point.x() - min.x()) / size.x(),
+            (point.y() - min.y()) / size.y(),
+            (point.z() - min.z()) / size.z(),
+        )
+    }
+
+    fn density(&self, local: &Point3) -> f64 {
+        self.config.density.sample(local) * self.config.density_scale
+    }
+
+    /// Walks the majorant cells crossed by `ray`, proposing tentative
+    /// collisions distributed according to each cell's majorant. `collide`
+    /// receives the tentative `t` and the ratio of the real density there to
+    /// the majorant, and returns true to stop walking.
+    fn track(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        ray: &Ray,
+        t_min: f64,
+        t_max: f64,
+        mut collide: impl FnMut(&mut dyn rand::RngCore, f64, f64) -> bool,
+    ) {
+        let (t_enter, t_exit) = match self.config.bounds.intersect(ray, t_min, t_max) {
+            Some(interval) => interval,
+            None => return,
+        };
+
+        // The ray in majorant cell coordinates. The mapping is affine, so `t`
+        // means the same thing in both spaces.
+        let cells = self.majorant.resolution();
+        let origin = self.to_local(&ray.origin());
+        let direction = self.to_local(&(ray.origin() + ray.direction())) - origin;
+        let ray_length = ray.direction().length();
+
+        let mut t = t_enter;
+        let mut cell = [0; 3];
+        let mut step = [0; 3];
+        let mut t_next = [f64::INFINITY; 3];
+        let mut t_delta = [f64::INFINITY; 3];
+        for axis in 0..3 {
+            let n = cells[axis] as f64;
+            let o = origin[axis] * n;
+            let d = direction[axis] * n;
+            let position = (o + d * t).floor().clamp(0.0, n - 1.0);
+            cell[axis] = position as isize;
+            if d > 0.0 {
+                step[axis] = 1;
+                t_next[axis] = (position + 1.0 - o) / d;
+                t_delta[axis] = 1.0 / d;
+            } else if d < 0.0 {
+                step[axis] = -1;
+                t_next[axis] = (position - o) / d;
+                t_delta[axis] = -1.0 / d;
+            }
+        }
+
+        loop {
+            let axis = if t_next[0] < t_next[1] {
+                if t_next[0] < t_next[2] {
+                    0
+                } else {
+                    2
+                }
+            } else if t_next[1] < t_next[2] {
+                1
+            } else {
+                2
+            };
+            let cell_exit = t_next[axis].min(t_exit);
+
+            let majorant =
+                self.majorant.voxel(cell[0], cell[1], cell[2]) * self.config.density_scale;
+            if majorant > 0.0 {
+                loop {
+                    let random: f64 = rng.gen_range(f64::EPSILON..=1.0);
+                    t -= random.ln() / (majorant * ray_length);
+                    if t >= cell_exit {
+                        break;
+                    }
+
+                    let ratio = self.density(&self.to_local(&ray.at(t))) / majorant;
+                    if collide(rng, t, ratio) {
+                        return;
+                    }
+                }
+            }
+
+            t = cell_exit;
+            if t >= t_exit {
+                return;
+            }
+
+            cell[axis] += step[axis];
+            if cell[axis] < 0 || cell[axis] >= cells[axis] as isize {
+                return;
+            }
+            t_next[axis] += t_delta[axis];
+        }
+    }
+}
+
+impl Hittable for GridMedium {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        // Delta tracking: accept each tentative collision with probability
+        // equal to the real density over the majorant
+        let mut collision = None;
+        self.volume.track(
+            &mut rand::thread_rng(),
+            ray,
+            t_min,
+            t_max,
+            |rng, t, ratio| {
+                if rng.gen_range(0.0..1.0) < ratio {
+                    collision = Some(t);
+                    true
+                } else {
+                    false
+                }
+            },
+        );
+
+        // The normal is arbitrary, since the phase function ignores it
+        collision.map(|t| {
+            HitResult::new(
+                ray,
+                ray.at(t),
+                Vec3::new(1.0, 0.0, 0.0),
+                t,
+                (0.0, 0.0),
+                Arc::clone(&self.phase_function),
+            )
+        })
+    }
+
+    fn bounding_box(&self, _time_range: Range<f64>) -> Option<Aabb> {
+        Some(self.volume.config.bounds.clone())
+    }
+}
+
+impl Material for VolumePhase {
+    fn scatter(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        hit: &HitResult,
+        ray_in: &Ray,
+    ) -> Option<ScatterResult> {
+        Some(ScatterResult {
+            scattered: Ray::new(hit.point(), Vec3::random_unit(rng), ray_in.time()),
+            attenuation: self.volume.config.albedo,
+        })
+    }
+
+    /// Collisions are found in proportion to extinction, so the absorbed
+    /// fraction (one minus the albedo) of them gathers the emission.
+    fn emitted(&self, hit: &HitResult, _ray_in: &Ray) -> Color {
+        let point = &hit.point();
+        let config = &self.volume.config;
+        let emission = match &config.emission {
+            VolumeEmission::None => return Color::zero(),
+            VolumeEmission::Grid { values, color } => {
+                values.sample(&self.volume.to_local(point)) * *color
+            }
+            VolumeEmission::Temperature { kelvin, intensity } => {
+                *intensity * blackbody(kelvin.sample(&self.volume.to_local(point)))
+            }
+        };
+        (Color::one() - config.albedo) * emission
+    }
+}
+
+/// Black body radiance sampled at red, green and blue wavelengths, relative to
+/// the green radiance of a 6500K body. Cooler bodies are much dimmer and
+/// redder.
+pub fn blackbody(kelvin: f64) -> Color {
+    // Planck's law, dropping constant factors that cancel in the ratio
+    let planck = |wavelength_nm: f64, kelvin: f64| {
+        const HC_OVER_K: f64 = 1.438_776_9e-2;
+        let wavelength = wavelength_nm * 1e-9;
+        1.0 / (wavelength.powi(5) * ((HC_OVER_K / (wavelength * kelvin)).exp() - 1.0))
+    };
+
+    if kelvin <= 0.0 {
+        return Color::zero();
+    }
+
+    let reference = planck(550.0, 6500.0);
+    Color::new(
+        planck(610.0, kelvin) / reference,
+        planck(550.0, kelvin) / reference,
+        planck(465.0, kelvin) / reference,
+    )
+}
@@ -0,0 +1,336 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    material::Material, texture::Texture, triangle::hit_triangle, Aabb, HitResult, Hittable,
+    Point3, Ray, Transform, Vec3,
+};
+
+/// Terrain described by a regular grid of heights over the XZ plane, centered
+/// on the transform's position. Each grid cell is split into two triangles,
+/// but rays only test the cells they pass over, found by walking the grid
+/// with a 2D DDA. Shading normals are interpolated between per-vertex normals
+/// taken from the slope of the grid, so the surface shades smoothly.
+pub struct Heightfield<T> {
+    transform: T,
+    size: (f64, f64),
+    resolution: [usize; 2],
+    /// Heights at each grid vertex, X varying fastest.
+    heights: Vec<f64>,
+    normals: Vec<Vec3>,
+    bounds: Aabb,
+    material: Arc<dyn Material>,
+}
+
+impl<T: Transform> Heightfield<T> {
+    /// A heightfield `size.0` wide along X and `size.1` deep along Z, with
+    /// `resolution` vertices along each of those axes. `height` gives the
+    /// height at an X, Z position relative to the center.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either resolution is less than 2
+    pub fn from_fn(
+        transform: T,
+        size: (f64, f64),
+        resolution: [usize; 2],
+        height: impl Fn(f64, f64) -> f64,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        assert!(
+            resolution[0] >= 2 && resolution[1] >= 2,
+            "A heightfield needs at least 2x2 vertices"
+        );
+
+        let [nx, nz] = resolution;
+        let mut heights = Vec::with_capacity(nx * nz);
+        for j in 0..nz {
+            for i in 0..nx {
+                let x = (i as f64 / (nx - 1) as f64 - 0.5) * size.0;
+                let z = (j as f64 / (nz - 1) as f64 - 0.5) * size.1;
+                heights.push(height(x, z));
+            }
+        }
+
+        let (min, max) = heights
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &h| {
+                (min.min(h), max.max(h))
+            });
+        let bounds = Aabb::new(
+            Point3::new(-0.5 * size.0, min, -0.5 * size.1),
+            Point3::new(0.5 * size.0, max, 0.5 * size.1),
+        )
+        .padded();
+
+        let mut heightfield = Self {
+            transform,
+            size,
+            resolution,
+            heights,
+            normals: Vec::new(),
+            bounds,
+            material,
+        };
+        heightfield.normals = heightfield.vertex_normals();
+        heightfield
+    }
+
+    /// A heightfield whose heights are the brightness (mean of the color
+    /// channels) of `texture` scaled by `height_scale`. The texture is looked
+    /// up with the same UV coordinates reported for hits, so an image used
+    /// for the heights also lines up as the terrain's color.
+    pub fn from_texture(
+        transform: T,
+        size: (f64, f64),
+        resolution: [usize; 2],
+        texture: &dyn Texture,
+        height_scale: f64,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self::from_fn(
+            transform,
+            size,
+            resolution,
+            |x, z| {
+                let uv = (x / size.0 + 0.5, z / size.1 + 0.5);
+                let color = texture.value(uv, &Point3::new(x, 0.0, z));
+                (color.x() + color.y() + color.z()) / 3.0 * height_scale
+            },
+            material,
+        )
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn resolution(&self) -> [usize; 2] {
+        self.resolution
+    }
+
+    fn cell_size(&self) -> (f64, f64) {
+        (
+            self.size.0 / (self.resolution[0] - 1) as f64,
+            self.size.1 / (self.resolution[1] - 1) as f64,
+        )
+    }
+
+    fn index(&self, i: usize, j: usize) -> usize {
+        j * self.resolution[0] + i
+    }
+
+    fn vertex(&self, i: usize, j: usize) -> Point3 {
+        let (dx, dz) = self.cell_size();
+        Point3::new(
+            i as f64 * dx - 0.5 * self.size.0,
+            self.heights[self.index(i, j)],
+            j as f64 * dz - 0.5 * self.size.1,
+        )
+    }
+
+    /// Normals from the slope of the grid, using central differences away
+    /// from the edges.
+    fn vertex_normals(&self) -> Vec<Vec3> {
+        let [nx, nz] = self.resolution;
+        let (dx, dz) = self.cell_size();
+        let mut normals = Vec::with_capacity(nx * nz);
+        for j in 0..nz {
+            for i in 0..nx {
+                let (i0, i1) = (i.saturating_sub(1), (i + 1).min(nx - 1));
+                let (j0, j1) = (j.saturating_sub(1), (j + 1).min(nz - 1));
+                let slope_x = (self.heights[self.index(i1, j)] - self.heights[self.index(i0, j)])
+                    / ((i1 - i0) as f64 * dx);
+                let slope_z = (self.heights[self.index(i, j1)] - self.heights[self.index(i, j0)])
+                    / ((j1 - j0) as f64 * dz);
+                normals.push(Vec3::new(-slope_x, 1.0, -slope_z).normalized());
+            }
+        }
+        normals
+    }
+
+    /// Intersects the two triangles of cell `(i, j)`, returning the nearest
+    /// hit's `t`, the triangle's normal and the interpolated normal.
+    fn hit_cell(
+        &self,
+        ray: &Ray,
+        i: usize,
+        j: usize,
+        t_min: f64,
+        t_max: f64,
+    ) -> Option<(f64, Vec3, Vec3)> {
+        let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+        let mut closest: Option<(f64, Vec3, Vec3)> = None;
+        for triangle in [[0, 1, 2], [0, 2, 3]] {
+            let [a, b, c] = triangle.map(|k| corners[k]);
+            let t_max = closest.map_or(t_max, |(t, _, _)| t);
+            let vertices = [a, b, c].map(|(i, j)| self.vertex(i, j));
+            if let Some((t, (b1, b2))) = hit_triangle(ray, &vertices, t_min, t_max) {
+                let [na, nb, nc] = [a, b, c].map(|(i, j)| self.normals[self.index(i, j)]);
+                let shading_normal = ((1.0 - b1 - b2) * na + b1 * nb + b2 * nc).normalized();
+                let [a, b, c] = vertices;
+                let normal = (c - a).cross(&(b - a)).normalized();
+                closest = Some((t, normal, shading_normal));
+            }
+        }
+        closest
+    }
+}
+
+impl<T: Transform> Hittable for Heightfield<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let position = self.transform.position(ray.time());
+        let local_ray = Ray::new(ray.origin() - position, ray.direction(), ray.time());
+        let (t_enter, t_exit) = self.bounds.intersect(&local_ray, t_min, t_max)?;
+
+        let origin = local_ray.origin();
+        let direction = local_ray.direction();
+        let cells = [self.resolution[0] - 1, self.resolution[1] - 1];
+        let (dx, dz) = self.cell_size();
+        let start = local_ray.at(t_enter);
+
+        // Walk the cells under the ray along X (axis 0) and Z (axis 1)
+        let mut cell = [0; 2];
+        let mut step = [0; 2];
+        let mut t_next = [f64::INFINITY; 2];
+        let mut t_delta = [f64::INFINITY; 2];
+        for (axis, &(o, d, size, extent)) in [
+            (origin.x(), direction.x(), dx, self.size.0),
+            (origin.z(), direction.z(), dz, self.size.1),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let p = [start.x(), start.z()][axis];
+            let n = cells[axis] as f64;
+            let position = ((p + 0.5 * extent) / size).floor().clamp(0.0, n - 1.0);
+            cell[axis] = position as isize;
+            let edge = |k: f64| k * size - 0.5 * extent;
+            if d > 0.0 {
+                step[axis] = 1;
+                t_next[axis] = (edge(position + 1.0) - o) / d;
+                t_delta[axis] = size / d;
+            } else if d < 0.0 {
+                step[axis] = -1;
+                t_next[axis] = (edge(position) - o) / d;
+                t_delta[axis] = -size / d;
+            }
+        }
+
+        let mut t = t_enter;
+        loop {
+            let axis = if t_next[0] < t_next[1] { 0 } else { 1 };
+            let cell_exit = t_next[axis].min(t_exit);
+
+            // Only test the triangles if the ray's height over the cell
+            // overlaps the cell's heights
+            let (i, j) = (cell[0] as usize, cell[1] as usize);
+            let corners = [(i, j), (i + 1, j), (i, j + 1), (i + 1, j + 1)];
+            let (low, high) = corners
+                .iter()
+                .map(|&(i, j)| self.heights[self.index(i, j)])
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), h| {
+                    (low.min(h), high.max(h))
+                });
+            let (y0, y1) = (
+                origin.y() + direction.y() * t,
+                origin.y() + direction.y() * cell_exit,
+            );
+            if y0.min(y1) <= high && y0.max(y1) >= low {
+                if let Some((t, normal, shading_normal)) =
+                    self.hit_cell(&local_ray, i, j, t_min, t_max)
+                {
+                    let local = local_ray.at(t);
+                    let uv = (local.x() / self.size.0 + 0.5, local.z() / self.size.1 + 0.5);
+                    return Some(
+                        HitResult::new(ray, ray.at(t), normal, t, uv, Arc::clone(&self.material))
+                            .with_shading_normal(shading_normal)
+                            .with_tangent(Vec3::new(1.0, 0.0, 0.0))
+                            .with_uv_scale(self.size.0, self.size.1),
+                    );
+                }
+            }
+
+            t = cell_exit;
+            if t >= t_exit {
+                return None;
+            }
+
+            cell[axis] += step[axis];
+            if cell[axis] < 0 || cell[axis] >= cells[axis] as isize {
+                return None;
+            }
+            t_next[axis] += t_delta[axis];
+        }
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        Some(Aabb::swept(&self.bounds, &self.transform, time_range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{material::Lambertian, texture::SolidColor, Color, StaticTransform};
+
+    #[test]
+    fn hits_match_testing_every_cell() {
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        let heightfield = Heightfield::from_fn(
+            StaticTransform::new(Point3::zero()),
+            (4.0, 3.0),
+            [17, 11],
+            |x, z| (2.0 * x).sin() * (3.0 * z).cos() + 0.3 * x,
+            material,
+        );
+        let [nx, nz] = heightfield.resolution();
+        let brute_force = |ray: &Ray| {
+            let mut t_max = f64::INFINITY;
+            for j in 0..nz - 1 {
+                for i in 0..nx - 1 {
+                    if let Some((t, _, _)) = heightfield.hit_cell(ray, i, j, 0.001, t_max) {
+                        t_max = t;
+                    }
+                }
+            }
+            Some(t_max).filter(|t| t.is_finite())
+        };
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut hits = 0;
+        for n in 0..2000 {
+            let origin = Point3::new(
+                rng.gen_range(-3.0..3.0),
+                rng.gen_range(-2.0..3.0),
+                rng.gen_range(-2.5..2.5),
+            );
+            // Some rays straight down, which don't step along either axis
+            let direction = if n % 10 == 0 {
+                Vec3::new(0.0, -1.0, 0.0)
+            } else {
+                Vec3::random_unit(&mut rng)
+            };
+            let ray = Ray::new(origin, direction, 0.0);
+            let expected = brute_force(&ray);
+            let actual = heightfield
+                .hit(&ray, 0.001, f64::INFINITY)
+                .map(|hit| hit.t());
+            match (expected, actual) {
+                (Some(expected), Some(actual)) => {
+                    assert!(
+                        (expected - actual).abs() < 1e-9,
+                        "{} != {}",
+                        expected,
+                        actual
+                    );
+                    hits += 1;
+                }
+                (None, None) => {}
+                _ => panic!("{:?} != {:?}", expected, actual),
+            }
+        }
+        assert!(hits > 200);
+    }
+}
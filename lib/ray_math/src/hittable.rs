@@ -1,19 +1,58 @@
 use std::{ops::Range, sync::Arc};
 
-use crate::{material::Material, Aabb, Point3, Ray, Vec3};
+use crate::{
+    material::Material, Aabb, Color, LaneMask, Lanes, Medium, Point3, Ray, RayPacket, Vec3,
+};
 
 pub trait Hittable: Send + Sync {
     fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult>;
     fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb>;
+
+    /// Intersects every active lane of `packet`. The default traces each ray
+    /// on its own; hittables with a cheap vectorizable test should override it.
+    ///
+    /// Only `Sphere` and `BvhNode` have packet tests so far. Every other
+    /// shape (meshes, quads, the analytic and implicit surfaces, CSG, media,
+    /// and so on) falls back to this scalar loop, so packets only pay off in
+    /// scenes dominated by spheres under a BVH.
+    fn hit_packet(
+        &self,
+        packet: &RayPacket,
+        t_min: f64,
+        t_max: &Lanes,
+        active: &LaneMask,
+    ) -> PacketHitResult {
+        let mut result = PacketHitResult::default();
+        for (lane, hit) in result.iter_mut().enumerate() {
+            if active[lane] {
+                *hit = self.hit(packet.ray(lane), t_min, t_max[lane]);
+            }
+        }
+        result
+    }
 }
 
+pub type PacketHitResult = [Option<HitResult>; crate::PACKET_WIDTH];
+
+/// Where a ray hit a surface. The geometric normal is the true normal of the
+/// surface, used to tell which side a ray is on. Shading happens in a separate
+/// frame of shading normal, tangent and bitangent, where the normal may be
+/// bent (e.g. interpolated between mesh vertices) and the tangent follows the
+/// direction of increasing `u`. Both normals face the side the ray came from.
+#[derive(Clone)]
 pub struct HitResult {
     point: Point3,
     normal: Vec3,
+    geometric_normal: Vec3,
+    tangent: Vec3,
+    bitangent: Vec3,
     t: f64,
     uv: (f64, f64),
+    uv_scale: (f64, f64),
+    color: Color,
     front_face: bool,
     material: Arc<dyn Material>,
+    interior_medium: Option<Arc<dyn Medium>>,
 }
 
 impl HitResult {
@@ -32,24 +71,114 @@ impl HitResult {
             -outward_normal
         };
 
+        let tangent = perpendicular(&normal);
         Self {
             point,
             normal,
+            geometric_normal: normal,
+            tangent,
+            bitangent: normal.cross(&tangent),
             t,
             uv,
+            uv_scale: (1.0, 1.0),
+            color: Color::one(),
             front_face,
             material,
+            interior_medium: None,
         }
     }
 
+    /// Replaces the shading normal, which is flipped if needed to face the
+    /// same side as the geometric normal. The tangent is kept as close as
+    /// possible to what it was.
+    pub fn with_shading_normal(mut self, normal: Vec3) -> Self {
+        self.normal = if normal.dot(&self.geometric_normal) < 0.0 {
+            -normal
+        } else {
+            normal
+        };
+        let tangent = self.tangent;
+        self.with_tangent(tangent)
+    }
+
+    /// Sets the direction the shading frame's tangent should follow, usually
+    /// the direction of increasing `u`. It is made perpendicular to the
+    /// shading normal, and ignored if it is parallel to it.
+    pub fn with_tangent(mut self, tangent: Vec3) -> Self {
+        let tangent = tangent - tangent.dot(&self.normal) * self.normal;
+        self.tangent = if tangent.length_squared() > 1e-16 {
+            tangent.normalized()
+        } else {
+            perpendicular(&self.normal)
+        };
+        self.bitangent = self.normal.cross(&self.tangent);
+        self
+    }
+
+    /// Sets how far the surface moves per unit of `u` along the tangent and
+    /// per unit of `v` along the bitangent, the lengths of its partial
+    /// derivatives by `u` and `v`.
+    pub fn with_uv_scale(mut self, u_scale: f64, v_scale: f64) -> Self {
+        self.uv_scale = (u_scale, v_scale);
+        self
+    }
+
+    /// Sets a color attribute of the surface at the hit, such as a
+    /// particle's color, which materials multiply into their albedo.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Moves the hit by `offset`, for shapes intersected in local space.
+    pub(crate) fn translated(mut self, offset: Vec3) -> Self {
+        self.point += offset;
+        self
+    }
+
+    /// The same hit with the surface's outward direction reversed, as if it
+    /// belonged to a solid on its other side.
+    pub fn flipped(mut self) -> Self {
+        self.front_face = !self.front_face;
+        self
+    }
+
     pub fn point(&self) -> Point3 {
         self.point
     }
 
+    /// The shading normal.
     pub fn normal(&self) -> Vec3 {
         self.normal
     }
 
+    pub fn geometric_normal(&self) -> Vec3 {
+        self.geometric_normal
+    }
+
+    pub fn tangent(&self) -> Vec3 {
+        self.tangent
+    }
+
+    pub fn bitangent(&self) -> Vec3 {
+        self.bitangent
+    }
+
+    /// Expresses a world-space direction in the shading frame, where the
+    /// tangent is X, the bitangent Y and the shading normal Z.
+    pub fn to_local(&self, direction: &Vec3) -> Vec3 {
+        Vec3::new(
+            direction.dot(&self.tangent),
+            direction.dot(&self.bitangent),
+            direction.dot(&self.normal),
+        )
+    }
+
+    /// The inverse of `to_local`.
+    pub fn to_world(&self, local: &Vec3) -> Vec3 {
+        local.x() * self.tangent + local.y() * self.bitangent + local.z() * self.normal
+    }
+
     pub fn t(&self) -> f64 {
         self.t
     }
@@ -58,6 +187,17 @@ impl HitResult {
         self.uv
     }
 
+    /// Distance moved over the surface per unit of `u` and `v`, one unless the
+    /// shape sets it.
+    pub fn uv_scale(&self) -> (f64, f64) {
+        self.uv_scale
+    }
+
+    /// The surface's color attribute, white unless set.
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
     pub fn front_face(&self) -> bool {
         self.front_face
     }
@@ -65,4 +205,24 @@ impl HitResult {
     pub fn material(&self) -> &dyn Material {
         self.material.as_ref()
     }
+
+    /// The medium on the inside of the surface that was hit, which a path
+    /// transmitted through the surface enters or leaves.
+    pub fn interior_medium(&self) -> Option<&Arc<dyn Medium>> {
+        self.interior_medium.as_ref()
+    }
+
+    pub fn set_interior_medium(&mut self, medium: Arc<dyn Medium>) {
+        self.interior_medium = Some(medium);
+    }
+}
+
+/// Some unit vector perpendicular to the unit vector `normal`.
+pub(crate) fn perpendicular(normal: &Vec3) -> Vec3 {
+    let helper = if normal.x().abs() > 0.9 {
+        Vec3::new(0.0, 1.0, 0.0)
+    } else {
+        Vec3::new(1.0, 0.0, 0.0)
+    };
+    helper.cross(normal).normalized()
 }
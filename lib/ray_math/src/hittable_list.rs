@@ -2,6 +2,7 @@ use std::{ops::Range, sync::Arc};
 
 use crate::{Aabb, HitResult, Hittable, Ray};
 
+#[derive(Default)]
 pub struct HittableList {
     list: Vec<Arc<dyn Hittable>>,
 }
@@ -56,9 +57,7 @@ impl Hittable for HittableList {
         for hittable in &self.list {
             match hittable.bounding_box(time_range.clone()) {
                 Some(aabb) => {
-                    result = result
-                        .map(|res| Aabb::surround(&res, &aabb))
-                        .or_else(|| Some(aabb));
+                    result = result.map(|res| Aabb::surround(&res, &aabb)).or(Some(aabb));
                 }
                 None => return None,
             }
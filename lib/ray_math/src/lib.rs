@@ -1,22 +1,68 @@
 mod aabb;
+mod bvh4;
 mod bvh_node;
 mod camera;
+mod cone;
+mod constant_medium;
+mod csg;
+mod cuboid;
+mod curve;
+mod cylinder;
+mod disk;
+mod displacement;
+mod grid_medium;
+mod heightfield;
 mod hittable;
 mod hittable_list;
+mod medium;
+mod mesh;
+mod metaballs;
+mod particles;
+mod quad;
 mod ray;
+mod ray_packet;
+mod roots;
+mod sdf_object;
 mod sphere;
+mod subdivision;
+mod torus;
 mod transform;
+mod triangle;
 mod vec3;
+mod voxel_grid;
 
 pub mod material;
+pub mod sdf;
+pub mod spectrum;
 pub mod texture;
 
 pub use aabb::*;
+pub use bvh4::*;
 pub use bvh_node::*;
 pub use camera::*;
+pub use cone::*;
+pub use constant_medium::*;
+pub use csg::*;
+pub use cuboid::*;
+pub use curve::*;
+pub use cylinder::*;
+pub use disk::*;
+pub use grid_medium::*;
+pub use heightfield::*;
 pub use hittable::*;
 pub use hittable_list::*;
+pub use medium::*;
+pub use mesh::*;
+pub use metaballs::*;
+pub use particles::*;
+pub use quad::*;
 pub use ray::*;
+pub use ray_packet::*;
+pub use roots::*;
+pub use sdf_object::*;
 pub use sphere::*;
+pub use subdivision::*;
+pub use torus::*;
 pub use transform::*;
 pub use vec3::*;
+pub use voxel_grid::*;
@@ -0,0 +1,199 @@
+use std::sync::Arc;
+
+use crate::{
+    texture::{SolidColor, Texture},
+    Color, HitResult, Ray, Vec3,
+};
+
+use super::{apply_normal_map, microfacet::Ggx, Material, NormalMap, ScatterResult};
+
+/// Measured complex indices of refraction of common metals, sampled at red,
+/// green and blue wavelengths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConductorPreset {
+    Aluminium,
+    Copper,
+    Gold,
+    Iron,
+    Silver,
+}
+
+impl ConductorPreset {
+    /// The real part `eta` and the extinction coefficient `k`.
+    pub fn index_of_refraction(&self) -> (Color, Color) {
+        match self {
+            ConductorPreset::Aluminium => (
+                Color::new(1.657, 0.880, 0.521),
+                Color::new(9.224, 6.270, 4.837),
+            ),
+            ConductorPreset::Copper => (
+                Color::new(0.200, 0.924, 1.102),
+                Color::new(3.912, 2.452, 2.142),
+            ),
+            ConductorPreset::Gold => (
+                Color::new(0.143, 0.374, 1.442),
+                Color::new(3.983, 2.385, 1.603),
+            ),
+            ConductorPreset::Iron => (
+                Color::new(2.912, 2.950, 2.585),
+                Color::new(3.089, 2.932, 2.767),
+            ),
+            ConductorPreset::Silver => (
+                Color::new(0.155, 0.117, 0.138),
+                Color::new(4.828, 3.122, 2.147),
+            ),
+        }
+    }
+}
+
+/// A metal made of microfacets following the GGX distribution, with Smith
+/// masking-shadowing and the Fresnel reflectance of a conductor with complex
+/// index of refraction `eta + i k`. Reflections are sampled from the normals
+/// visible from the incoming direction, so every sample carries the
+/// reflectance and shadowing that direction sees.
+///
+/// Roughness is the brightness of a texture, from 0 (a mirror) to 1, and is
+/// squared to give the width of the distribution.
+pub struct Conductor {
+    eta: Color,
+    k: Color,
+    roughness: Arc<dyn Texture>,
+    normal_map: Option<NormalMap>,
+}
+
+impl Conductor {
+    pub fn new(eta: Color, k: Color, roughness: Arc<dyn Texture>) -> Self {
+        Self {
+            eta,
+            k,
+            roughness,
+            normal_map: None,
+        }
+    }
+
+    /// One of the measured metals, with the same roughness everywhere.
+    pub fn preset(preset: ConductorPreset, roughness: f64) -> Self {
+        let (eta, k) = preset.index_of_refraction();
+        Self::new(eta, k, Arc::new(SolidColor::new(Color::one() * roughness)))
+    }
+
+    pub fn with_normal_map(mut self, normal_map: NormalMap) -> Self {
+        self.normal_map = Some(normal_map);
+        self
+    }
+
+    fn roughness(&self, hit: &HitResult) -> f64 {
+        let color = self.roughness.value(hit.uv(), &hit.point());
+        (color.x() + color.y() + color.z()) / 3.0
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        hit: &HitResult,
+        ray_in: &Ray,
+    ) -> Option<ScatterResult> {
+        let hit = apply_normal_map(&self.normal_map, hit);
+        let wo = hit.to_local(&-ray_in.direction().normalized());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let ggx = Ggx::from_roughness(self.roughness(&hit));
+        let microfacet_normal = ggx.sample_visible_normal(rng, &wo);
+        let wi = Vec3::reflect(&-wo, &microfacet_normal);
+        if wi.z() <= 0.0 {
+            return None;
+        }
+        let direction = hit.to_world(&wi);
+        if direction.dot(&hit.geometric_normal()) <= 0.0 {
+            return None;
+        }
+
+        let fresnel = fresnel_conductor(wo.dot(&microfacet_normal), &self.eta, &self.k);
+        let shadowing = ggx.masking_shadowing(&wo, &wi) / ggx.masking(&wo);
+        Some(ScatterResult {
+            scattered: Ray::new(hit.point(), direction, ray_in.time()),
+            attenuation: fresnel * shadowing * hit.color(),
+        })
+    }
+}
+
+/// Fresnel reflectance of unpolarized light on a conductor, per channel.
+pub(crate) fn fresnel_conductor(cos_theta: f64, eta: &Color, k: &Color) -> Color {
+    let mut reflectance = Color::zero();
+    for channel in 0..3 {
+        reflectance[channel] = fresnel_complex(cos_theta, eta[channel], k[channel]);
+    }
+    reflectance
+}
+
+/// Fresnel reflectance at an interface with complex index of refraction
+/// `eta + i k`, from pbrt's `FrConductor`.
+fn fresnel_complex(cos_theta: f64, eta: f64, k: f64) -> f64 {
+    let cos2 = cos_theta.clamp(0.0, 1.0).powi(2);
+    let sin2 = 1.0 - cos2;
+    let (eta2, k2) = (eta * eta, k * k);
+
+    let t0 = eta2 - k2 - sin2;
+    let a2_plus_b2 = (t0 * t0 + 4.0 * eta2 * k2).sqrt();
+    let t1 = a2_plus_b2 + cos2;
+    let a = (0.5 * (a2_plus_b2 + t0)).max(0.0).sqrt();
+    let t2 = 2.0 * cos_theta * a;
+    let perpendicular = (t1 - t2) / (t1 + t2);
+
+    let t3 = cos2 * a2_plus_b2 + sin2 * sin2;
+    let t4 = t2 * sin2;
+    let parallel = perpendicular * (t3 - t4) / (t3 + t4);
+
+    0.5 * (parallel + perpendicular)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{Hittable, Point3, Quad, StaticTransform};
+
+    #[test]
+    fn fresnel_at_normal_incidence_and_in_range() {
+        let (eta, k) = ConductorPreset::Gold.index_of_refraction();
+        let reflectance = fresnel_conductor(1.0, &eta, &k);
+        for channel in 0..3 {
+            let (n, k) = (eta[channel], k[channel]);
+            let expected = ((n - 1.0).powi(2) + k * k) / ((n + 1.0).powi(2) + k * k);
+            assert!((reflectance[channel] - expected).abs() < 1e-9);
+        }
+        for step in 0..=100 {
+            let reflectance = fresnel_conductor(step as f64 / 100.0, &eta, &k);
+            for channel in 0..3 {
+                assert!((0.0..=1.0).contains(&reflectance[channel]));
+            }
+        }
+    }
+
+    #[test]
+    fn smooth_conductor_is_a_mirror() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let conductor = Arc::new(Conductor::preset(ConductorPreset::Silver, 0.0));
+        let quad = Quad::from(
+            StaticTransform::new(Point3::zero()),
+            Point3::new(-1.0, 0.0, -1.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            conductor.clone(),
+        );
+        let ray = Ray::new(Point3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 0.0), 0.0);
+        let hit = quad.hit(&ray, 0.0, f64::INFINITY).unwrap();
+        let result = conductor.scatter(&mut rng, &hit, &ray).unwrap();
+        // Up to the smallest width the distribution is given
+        let expected = Vec3::new(1.0, 1.0, 0.0).normalized();
+        assert!((result.scattered.direction().normalized() - expected).length() < 1e-3);
+        let (eta, k) = ConductorPreset::Silver.index_of_refraction();
+        let fresnel = fresnel_conductor(0.5f64.sqrt(), &eta, &k);
+        assert!((result.attenuation - fresnel).length() < 1e-3);
+    }
+}
@@ -1,20 +1,61 @@
+use std::sync::Arc;
+
 use rand::Rng;
 
-use crate::{Color, Ray, Vec3};
+use crate::{spectrum::sample_wavelength, Color, HomogeneousMedium, Medium, Ray, Vec3};
 
-use super::{Material, ScatterResult};
+use super::{apply_normal_map, Dispersion, Material, NormalMap, ScatterResult};
 
 pub struct Dielectric {
     index_of_refraction: f64,
+    normal_map: Option<NormalMap>,
+    absorption: Option<Arc<dyn Medium>>,
+    tint: Color,
+    dispersion: Option<Dispersion>,
 }
 
 impl Dielectric {
     pub fn new(index_of_refraction: f64) -> Self {
         Self {
             index_of_refraction,
+            normal_map: None,
+            absorption: None,
+            tint: Color::one(),
+            dispersion: None,
         }
     }
 
+    /// Makes the index of refraction depend on wavelength, replacing the
+    /// fixed one. A path reaching the glass without a wavelength picks one and
+    /// carries only that wavelength from then on, so colors separate where it
+    /// refracts.
+    pub fn with_dispersion(mut self, dispersion: Dispersion) -> Self {
+        self.dispersion = Some(dispersion);
+        self
+    }
+
+    /// Colors the glass by absorbing `sigma_a` per unit distance travelled
+    /// inside it, so thick parts look darker and more saturated than thin
+    /// ones (Beer-Lambert). Only works on closed shapes, whose back faces the
+    /// light leaves through.
+    pub fn with_absorption(mut self, sigma_a: Color) -> Self {
+        self.absorption = Some(Arc::new(HomogeneousMedium::new(sigma_a, Color::zero())));
+        self
+    }
+
+    /// Multiplies light by `tint` each time it's transmitted through the
+    /// surface, for glass too thin to model its thickness, such as a window
+    /// made of a single quad.
+    pub fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    pub fn with_normal_map(mut self, normal_map: NormalMap) -> Self {
+        self.normal_map = Some(normal_map);
+        self
+    }
+
     fn reflectance(cosine: f64, ref_index: f64) -> f64 {
         let mut r0 = (1.0 - ref_index) / (1.0 + ref_index);
         r0 *= r0;
@@ -29,10 +70,28 @@ impl Material for Dielectric {
         hit: &crate::HitResult,
         ray_in: &Ray,
     ) -> Option<ScatterResult> {
+        let hit = apply_normal_map(&self.normal_map, hit);
+        let (index_of_refraction, wavelength, spectral_weight) =
+            match (&self.dispersion, ray_in.wavelength()) {
+                (None, wavelength) => (self.index_of_refraction, wavelength, Color::one()),
+                (Some(dispersion), Some(wavelength)) => (
+                    dispersion.index_of_refraction(wavelength),
+                    Some(wavelength),
+                    Color::one(),
+                ),
+                (Some(dispersion), None) => {
+                    let (wavelength, weight) = sample_wavelength(rng);
+                    (
+                        dispersion.index_of_refraction(wavelength),
+                        Some(wavelength),
+                        weight,
+                    )
+                }
+            };
         let refraction_ratio = if hit.front_face() {
-            1.0 / self.index_of_refraction
+            1.0 / index_of_refraction
         } else {
-            self.index_of_refraction
+            index_of_refraction
         };
 
         let unit_direction = ray_in.direction().normalized();
@@ -40,17 +99,80 @@ impl Material for Dielectric {
         let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
 
         let cannot_refract = refraction_ratio * sin_theta > 1.0;
-        let direction = if cannot_refract
+        let (direction, attenuation) = if cannot_refract
             || Self::reflectance(cos_theta, refraction_ratio) > rng.gen_range(0.0..=1.0)
         {
-            Vec3::reflect(&unit_direction, &hit.normal())
+            (Vec3::reflect(&unit_direction, &hit.normal()), Color::one())
         } else {
-            Vec3::refract(&unit_direction, &hit.normal(), refraction_ratio)
+            (
+                Vec3::refract(&unit_direction, &hit.normal(), refraction_ratio),
+                self.tint,
+            )
         };
 
+        let scattered = Ray::new(hit.point(), direction, ray_in.time());
         Some(ScatterResult {
-            scattered: Ray::new(hit.point(), direction, ray_in.time()),
-            attenuation: Color::one(),
+            scattered: match wavelength {
+                Some(wavelength) => scattered.with_wavelength(wavelength),
+                None => scattered,
+            },
+            attenuation: attenuation * spectral_weight,
         })
     }
+
+    fn interior_medium(&self) -> Option<&Arc<dyn Medium>> {
+        self.absorption.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{Hittable, Point3, Quad, StaticTransform};
+
+    #[test]
+    fn absorption_follows_beer_lambert() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let sigma_a = Color::new(0.1, 0.5, 2.0);
+        let glass = Dielectric::new(1.5).with_absorption(sigma_a);
+        let medium = glass.interior_medium().unwrap();
+        // Two units of distance, along a direction that isn't unit length
+        let ray = Ray::new(Point3::zero(), Vec3::new(0.0, 0.0, 0.5), 0.0);
+        let sample = medium.sample(&mut rng, &ray, 4.0);
+        assert!(sample.scatter_t.is_none());
+        for channel in 0..3 {
+            let expected = (-2.0 * sigma_a[channel]).exp();
+            assert!((sample.weight[channel] - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn tint_only_applies_to_transmission() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let tint = Color::new(0.2, 0.6, 0.9);
+        let glass = Arc::new(Dielectric::new(1.5).with_tint(tint));
+        let quad = Quad::from(
+            StaticTransform::new(Point3::zero()),
+            Point3::new(-1.0, 0.0, -1.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            glass.clone(),
+        );
+        let ray = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0);
+        let hit = quad.hit(&ray, 0.0, f64::INFINITY).unwrap();
+        let (mut reflected, mut transmitted) = (0, 0);
+        for _ in 0..1000 {
+            let result = glass.scatter(&mut rng, &hit, &ray).unwrap();
+            if result.scattered.direction().y() > 0.0 {
+                assert_eq!(result.attenuation, Color::one());
+                reflected += 1;
+            } else {
+                assert_eq!(result.attenuation, tint);
+                transmitted += 1;
+            }
+        }
+        assert!(reflected > 0 && transmitted > reflected);
+    }
 }
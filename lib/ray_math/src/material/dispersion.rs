@@ -0,0 +1,40 @@
+/// How a transparent material's index of refraction changes with wavelength,
+/// which splits white light into its colors. Coefficients are for wavelengths
+/// in micrometers, as they're usually published.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dispersion {
+    /// `n = a + b / λ²`, a good fit for most glasses over the visible range.
+    Cauchy { a: f64, b: f64 },
+    /// `n² = 1 + Σ b λ² / (λ² - c)`, accurate from the ultraviolet to the
+    /// infrared.
+    Sellmeier { b: [f64; 3], c: [f64; 3] },
+}
+
+impl Dispersion {
+    /// Borosilicate crown glass, common for lenses.
+    pub const BK7: Dispersion = Dispersion::Sellmeier {
+        b: [1.039_612_12, 0.231_792_344, 1.010_469_45],
+        c: [0.006_000_698_67, 0.020_017_914_4, 103.560_653],
+    };
+    /// Dense flint glass, which disperses strongly.
+    pub const SF11: Dispersion = Dispersion::Sellmeier {
+        b: [1.737_596_95, 0.313_747_346, 1.898_781_01],
+        c: [0.013_188_707, 0.062_306_814_2, 155.236_29],
+    };
+    pub const DIAMOND: Dispersion = Dispersion::Sellmeier {
+        b: [0.3306, 4.3356, 0.0],
+        c: [0.030_625, 0.011_236, 0.0],
+    };
+
+    /// The index of refraction at `wavelength` nanometers.
+    pub fn index_of_refraction(&self, wavelength: f64) -> f64 {
+        let micrometers = wavelength * 1e-3;
+        let l2 = micrometers * micrometers;
+        match self {
+            Dispersion::Cauchy { a, b } => a + b / l2,
+            Dispersion::Sellmeier { b, c } => {
+                (1.0 + b.iter().zip(c).map(|(b, c)| b * l2 / (l2 - c)).sum::<f64>()).sqrt()
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+
+use crate::{Color, HitResult, Ray, Vec3};
+
+use super::{rough_dielectric::fresnel_dielectric, Material, ScatterResult};
+
+/// A hair fiber modelled as a rough dielectric cylinder with pigment inside,
+/// after Marschner et al. and d'Eon et al. Light either reflects off the
+/// surface (R), passes through the fiber (TT), or reflects once inside it
+/// (TRT); light that bounces around inside more than that is folded into TRT.
+/// Each lobe scatters into a cone around the fiber's tangent, tilted by the
+/// cuticle scales, with the azimuth set by where across the fiber it was hit.
+///
+/// Expects hits from a `Curve`, which give the tangent along the fiber and
+/// `v` across it.
+pub struct Hair {
+    absorption: Color,
+    longitudinal_roughness: f64,
+    azimuthal_roughness: f64,
+    scale_tilt: f64,
+    index_of_refraction: f64,
+}
+
+impl Hair {
+    /// A fiber absorbing `absorption` per unit of its diameter travelled.
+    pub fn new(absorption: Color) -> Self {
+        Self {
+            absorption,
+            longitudinal_roughness: 0.3,
+            azimuthal_roughness: 0.3,
+            scale_tilt: 2.0_f64.to_radians(),
+            index_of_refraction: 1.55,
+        }
+    }
+
+    /// A fiber colored by its concentrations of the dark brown eumelanin and
+    /// the reddish pheomelanin. Eumelanin of about 0.3 gives blonde hair, 1.3
+    /// brown and 8 black.
+    pub fn from_melanin(eumelanin: f64, pheomelanin: f64) -> Self {
+        Self::new(
+            eumelanin * Color::new(0.419, 0.697, 1.37) + pheomelanin * Color::new(0.187, 0.4, 1.05),
+        )
+    }
+
+    /// Sets how far the lobes spread along the fiber and around it, from 0
+    /// (smooth) to 1.
+    pub fn with_roughness(mut self, longitudinal: f64, azimuthal: f64) -> Self {
+        self.longitudinal_roughness = longitudinal;
+        self.azimuthal_roughness = azimuthal;
+        self
+    }
+
+    /// Sets the angle of the cuticle scales, which shifts the highlights
+    /// along the fiber.
+    pub fn with_scale_tilt(mut self, degrees: f64) -> Self {
+        self.scale_tilt = degrees.to_radians();
+        self
+    }
+
+    pub fn with_index_of_refraction(mut self, index_of_refraction: f64) -> Self {
+        self.index_of_refraction = index_of_refraction;
+        self
+    }
+
+    /// Longitudinal standard deviation of each lobe in radians.
+    fn longitudinal_spread(&self, lobe: usize) -> f64 {
+        let beta = self.longitudinal_roughness;
+        let spread = 0.726 * beta + 0.812 * beta * beta + 3.7 * beta.powi(20);
+        spread * [1.0, 0.5, 2.0][lobe]
+    }
+
+    /// Scale of the logistic distribution around each lobe's azimuth.
+    fn azimuthal_spread(&self) -> f64 {
+        let beta = self.azimuthal_roughness;
+        0.626_657_069 * (0.265 * beta + 1.194 * beta * beta + 5.372 * beta.powi(22))
+    }
+}
+
+impl Material for Hair {
+    fn scatter(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        hit: &HitResult,
+        ray_in: &Ray,
+    ) -> Option<ScatterResult> {
+        // Frame around the fiber: along it, towards the viewer and across it
+        let outgoing = -ray_in.direction().normalized();
+        let tangent = hit.tangent();
+        let sin_theta_o = outgoing.dot(&tangent).clamp(-1.0, 1.0);
+        let cos_theta_o = (1.0 - sin_theta_o * sin_theta_o).sqrt();
+        let toward_viewer = outgoing - sin_theta_o * tangent;
+        let toward_viewer = if toward_viewer.nearly_zero() {
+            hit.geometric_normal()
+        } else {
+            toward_viewer.normalized()
+        };
+        let across = tangent.cross(&toward_viewer);
+
+        let h = (2.0 * hit.uv().1 - 1.0).clamp(-1.0, 1.0);
+        let gamma_o = h.asin();
+
+        // Refraction into the fiber, projected onto the plane across it
+        let eta = self.index_of_refraction;
+        let sin_theta_t = sin_theta_o / eta;
+        let cos_theta_t = (1.0 - sin_theta_t * sin_theta_t).sqrt();
+        let eta_projected = (eta * eta - sin_theta_o * sin_theta_o).sqrt() / cos_theta_o.max(1e-6);
+        let sin_gamma_t = (h / eta_projected).clamp(-1.0, 1.0);
+        let gamma_t = sin_gamma_t.asin();
+        let cos_gamma_t = (1.0 - sin_gamma_t * sin_gamma_t).sqrt();
+
+        // How much light each lobe carries
+        let f = fresnel_dielectric(cos_theta_o * gamma_o.cos(), eta);
+        let path_length = 2.0 * cos_gamma_t / cos_theta_t;
+        let transmittance = Color::new(
+            (-self.absorption.x() * path_length).exp(),
+            (-self.absorption.y() * path_length).exp(),
+            (-self.absorption.z() * path_length).exp(),
+        );
+        let transmitted = (1.0 - f) * (1.0 - f) * transmittance;
+        let mut remaining = f * f * transmitted * transmittance * transmittance;
+        for channel in 0..3 {
+            remaining[channel] /= 1.0 - f * transmittance[channel];
+        }
+        let attenuations = [
+            f * Color::one(),
+            transmitted,
+            f * transmitted * transmittance + remaining,
+        ];
+
+        let weights = attenuations.map(|a| (a.x() + a.y() + a.z()) / 3.0);
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut pick = rng.gen_range(0.0..total);
+        let lobe = (0..2)
+            .find(|&lobe| {
+                pick -= weights[lobe];
+                pick < 0.0
+            })
+            .unwrap_or(2);
+        if weights[lobe] <= 0.0 {
+            return None;
+        }
+        let attenuation = attenuations[lobe] * (total / weights[lobe]);
+
+        // Mirror around the fiber, shifted by the cuticle scales, then spread
+        let shift = [-2.0, 1.0, 4.0][lobe] * self.scale_tilt;
+        let theta =
+            (-sin_theta_o.asin() + shift + self.longitudinal_spread(lobe) * standard_normal(rng))
+                .clamp(-0.5 * PI, 0.5 * PI);
+
+        let p = lobe as f64;
+        let phi = 2.0 * p * gamma_t - 2.0 * gamma_o
+            + p * PI
+            + trimmed_logistic(rng, self.azimuthal_spread());
+
+        let direction: Vec3 =
+            theta.sin() * tangent + theta.cos() * (phi.cos() * toward_viewer + phi.sin() * across);
+        Some(ScatterResult {
+            scattered: Ray::new(hit.point(), direction, ray_in.time()),
+            attenuation,
+        })
+    }
+}
+
+/// A sample from the standard normal distribution, by the Box-Muller
+/// transform.
+fn standard_normal(rng: &mut dyn rand::RngCore) -> f64 {
+    let u1: f64 = 1.0 - rng.gen_range(0.0..1.0);
+    let u2: f64 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+/// A sample from the logistic distribution with scale `s`, limited to
+/// `[-π, π]`.
+fn trimmed_logistic(rng: &mut dyn rand::RngCore, s: f64) -> f64 {
+    let cdf = |x: f64| 1.0 / (1.0 + (-x / s).exp());
+    let (low, high) = (cdf(-PI), cdf(PI));
+    let u = low + rng.gen_range(0.0..1.0) * (high - low);
+    (-s * (1.0 / u - 1.0).ln()).clamp(-PI, PI)
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::{texture::Texture, Ray, Vec3};
+
+use super::{Material, ScatterResult};
+
+/// Phase function that scatters uniformly in all directions, used for the
+/// particles inside participating media.
+pub struct Isotropic {
+    albedo: Arc<dyn Texture>,
+}
+
+impl Isotropic {
+    pub fn new(albedo: Arc<dyn Texture>) -> Self {
+        Self { albedo }
+    }
+
+    pub fn albedo(&self) -> &dyn Texture {
+        &*self.albedo
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        hit: &crate::HitResult,
+        ray_in: &Ray,
+    ) -> Option<ScatterResult> {
+        Some(ScatterResult {
+            scattered: Ray::new(hit.point(), Vec3::random_unit(rng), ray_in.time()),
+            attenuation: self.albedo.value(hit.uv(), &hit.point()),
+        })
+    }
+}
@@ -2,15 +2,24 @@ use std::sync::Arc;
 
 use crate::{texture::Texture, Ray, Vec3};
 
-use super::{Material, ScatterResult};
+use super::{apply_normal_map, Material, NormalMap, ScatterResult};
 
 pub struct Lambertian {
     albedo: Arc<dyn Texture>,
+    normal_map: Option<NormalMap>,
 }
 
 impl Lambertian {
     pub fn new(albedo: Arc<dyn Texture>) -> Self {
-        Self { albedo }
+        Self {
+            albedo,
+            normal_map: None,
+        }
+    }
+
+    pub fn with_normal_map(mut self, normal_map: NormalMap) -> Self {
+        self.normal_map = Some(normal_map);
+        self
     }
 
     pub fn albedo(&self) -> &dyn Texture {
@@ -25,15 +34,24 @@ impl Material for Lambertian {
         hit: &crate::HitResult,
         ray_in: &Ray,
     ) -> Option<ScatterResult> {
+        let hit = apply_normal_map(&self.normal_map, hit);
         let mut scatter_direction = hit.normal() + Vec3::random_unit(rng);
         // Catch degenerate scatter direction
         if scatter_direction.nearly_zero() {
             scatter_direction = hit.normal();
         }
 
+        // A bent shading normal can send the path into the surface, so mirror
+        // it back out
+        let geometric_normal = hit.geometric_normal();
+        let below = scatter_direction.dot(&geometric_normal);
+        if below < 0.0 {
+            scatter_direction -= 2.0 * below * geometric_normal;
+        }
+
         Some(ScatterResult {
             scattered: Ray::new(hit.point(), scatter_direction, ray_in.time()),
-            attenuation: self.albedo.value(hit.uv(), &hit.point()),
+            attenuation: self.albedo.value(hit.uv(), &hit.point()) * hit.color(),
         })
     }
 }
@@ -1,4 +1,6 @@
-use crate::{Color, HitResult, Ray};
+use std::sync::Arc;
+
+use crate::{Color, HitResult, Medium, Ray};
 
 pub trait Material: Send + Sync {
     fn scatter(
@@ -7,6 +9,20 @@ pub trait Material: Send + Sync {
         hit: &HitResult,
         ray_in: &Ray,
     ) -> Option<ScatterResult>;
+
+    /// Light given off at a hit back along `ray_in`, independent of any light
+    /// arriving there.
+    fn emitted(&self, _hit: &HitResult, _ray_in: &Ray) -> Color {
+        Color::zero()
+    }
+
+    /// A medium filling the inside of surfaces made of this material, entered
+    /// when a path is transmitted through a front face and left through a
+    /// back face. A medium set on the hit (by `MediumBoundary`) takes
+    /// precedence.
+    fn interior_medium(&self) -> Option<&Arc<dyn Medium>> {
+        None
+    }
 }
 
 pub struct ScatterResult {
@@ -1,15 +1,25 @@
 use crate::{Color, Ray, Vec3};
 
-use super::{Material, ScatterResult};
+use super::{apply_normal_map, Material, NormalMap, ScatterResult};
 
 pub struct Metal {
     albedo: Color,
     fuzz: f64,
+    normal_map: Option<NormalMap>,
 }
 
 impl Metal {
     pub fn new(albedo: Color, fuzz: f64) -> Self {
-        Self { albedo, fuzz }
+        Self {
+            albedo,
+            fuzz,
+            normal_map: None,
+        }
+    }
+
+    pub fn with_normal_map(mut self, normal_map: NormalMap) -> Self {
+        self.normal_map = Some(normal_map);
+        self
     }
 }
 
@@ -20,16 +30,19 @@ impl Material for Metal {
         hit: &crate::HitResult,
         ray_in: &Ray,
     ) -> Option<ScatterResult> {
+        let hit = apply_normal_map(&self.normal_map, hit);
         let reflected = Vec3::reflect(&ray_in.direction().normalized(), &hit.normal());
         let scattered = Ray::new(
             hit.point(),
             reflected + self.fuzz * Vec3::random_in_unit_sphere(rng),
             ray_in.time(),
         );
-        if scattered.direction().dot(&hit.normal()) > 0.0 {
+        if scattered.direction().dot(&hit.normal()) > 0.0
+            && scattered.direction().dot(&hit.geometric_normal()) > 0.0
+        {
             Some(ScatterResult {
                 scattered,
-                attenuation: self.albedo,
+                attenuation: self.albedo * hit.color(),
             })
         } else {
             None
@@ -0,0 +1,80 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+
+use crate::Vec3;
+
+/// Smallest roughness used, below which the distribution is too sharp to
+/// evaluate reliably. Surfaces that smooth look like perfect mirrors anyway.
+const MIN_ALPHA: f64 = 1e-4;
+
+/// The GGX (Trowbridge-Reitz) distribution of microfacet normals with Smith's
+/// height-correlated masking-shadowing. Directions are in the shading frame,
+/// where the macro surface normal is Z.
+pub(crate) struct Ggx {
+    alpha: f64,
+}
+
+impl Ggx {
+    /// Roughness is perceptual, in [0,1], and squared to get the width of the
+    /// distribution.
+    pub(crate) fn from_roughness(roughness: f64) -> Self {
+        let roughness = roughness.clamp(0.0, 1.0);
+        Self {
+            alpha: (roughness * roughness).max(MIN_ALPHA),
+        }
+    }
+
+    /// Smith's auxiliary function for the distribution.
+    fn lambda(&self, w: &Vec3) -> f64 {
+        let cos2 = w.z() * w.z();
+        if cos2 == 0.0 {
+            return f64::INFINITY;
+        }
+        let tan2 = (1.0 - cos2).max(0.0) / cos2;
+        0.5 * ((1.0 + self.alpha * self.alpha * tan2).sqrt() - 1.0)
+    }
+
+    /// Fraction of the microfacets that are visible from `w`.
+    pub(crate) fn masking(&self, w: &Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    /// Fraction of microfacets visible from both `wo` and `wi`.
+    pub(crate) fn masking_shadowing(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    /// Samples a microfacet normal in proportion to how much of it is visible
+    /// from `wo`, after Heitz's "Sampling the GGX Distribution of Visible
+    /// Normals". `wo` must be above the surface.
+    pub(crate) fn sample_visible_normal(&self, rng: &mut dyn rand::RngCore, wo: &Vec3) -> Vec3 {
+        // Stretch to the configuration where the distribution is a hemisphere
+        let stretched = Vec3::new(self.alpha * wo.x(), self.alpha * wo.y(), wo.z()).normalized();
+        let t1 = if stretched.z() < 0.999_999 {
+            Vec3::new(0.0, 0.0, 1.0).cross(&stretched).normalized()
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let t2 = stretched.cross(&t1);
+
+        // Sample the projected hemisphere, squashing the half of the disk
+        // that's hidden from `wo`
+        let radius = rng.gen_range(0.0_f64..1.0).sqrt();
+        let phi = 2.0 * PI * rng.gen_range(0.0..1.0);
+        let p1 = radius * phi.cos();
+        let mut p2 = radius * phi.sin();
+        let s = 0.5 * (1.0 + stretched.z());
+        p2 = (1.0 - s) * (1.0 - p1 * p1).max(0.0).sqrt() + s * p2;
+        let p3 = (1.0 - p1 * p1 - p2 * p2).max(0.0).sqrt();
+        let normal = p1 * t1 + p2 * t2 + p3 * stretched;
+
+        // Unstretch
+        Vec3::new(
+            self.alpha * normal.x(),
+            self.alpha * normal.y(),
+            normal.z().max(1e-6),
+        )
+        .normalized()
+    }
+}
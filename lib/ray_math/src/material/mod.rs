@@ -1,9 +1,31 @@
+mod conductor;
 mod dielectric;
+mod dispersion;
+mod hair;
+mod isotropic;
 mod lambertian;
+mod layered;
+#[allow(clippy::module_inception)]
 mod material;
 mod metal;
+mod microfacet;
+mod normal_map;
+mod pass_through;
+mod principled;
+mod rough_dielectric;
+mod subsurface;
 
+pub use conductor::*;
 pub use dielectric::*;
+pub use dispersion::*;
+pub use hair::*;
+pub use isotropic::*;
 pub use lambertian::*;
+pub use layered::*;
 pub use material::*;
 pub use metal::*;
+pub use normal_map::*;
+pub use pass_through::*;
+pub use principled::*;
+pub use rough_dielectric::*;
+pub use subsurface::*;
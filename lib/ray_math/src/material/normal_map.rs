@@ -0,0 +1,123 @@
+use std::{borrow::Cow, sync::Arc};
+
+use crate::{texture::Texture, Color, HitResult, Vec3};
+
+/// How a material bends the shading normal of the surfaces it's applied to, to
+/// add detail that isn't in the geometry.
+pub enum NormalMap {
+    /// Colors are normals in the hit's shading frame, with each channel mapped
+    /// from [-1,1] to [0,1]: red along the tangent, green along the bitangent
+    /// and blue along the normal, so flat is `(0.5, 0.5, 1.0)`.
+    TangentSpace(Arc<dyn Texture>),
+    /// The surface is pushed out along its normal by the brightness of
+    /// `height`, scaled by `strength`. Slopes are found from small steps in UV,
+    /// converted to distances with the hit's UV scale, plus small steps of the
+    /// texture's point lookup along the tangent and bitangent.
+    Bump {
+        height: Arc<dyn Texture>,
+        strength: f64,
+    },
+}
+
+impl NormalMap {
+    fn normal(&self, hit: &HitResult) -> Vec3 {
+        match self {
+            NormalMap::TangentSpace(texture) => {
+                let color = texture.value(hit.uv(), &hit.point());
+                hit.to_world(&(2.0 * color - Color::one())).normalized()
+            }
+            NormalMap::Bump { height, strength } => {
+                const UV_STEP: f64 = 1e-3;
+                const WORLD_STEP: f64 = 1e-4;
+                let brightness = |color: Color| (color.x() + color.y() + color.z()) / 3.0;
+                let (u, v) = hit.uv();
+                let (u_scale, v_scale) = hit.uv_scale();
+                let point = hit.point();
+                let base = brightness(height.value((u, v), &point));
+                // The texture may vary with both UV and point, so the slope
+                // along each direction is the sum of the two
+                let slope = |uv: (f64, f64), uv_scale: f64, direction: Vec3| {
+                    let along_uv = brightness(height.value(uv, &point));
+                    let along_point =
+                        brightness(height.value((u, v), &(point + WORLD_STEP * direction)));
+                    (along_uv - base) / (UV_STEP * uv_scale.max(1e-6))
+                        + (along_point - base) / WORLD_STEP
+                };
+                let slope_tangent = slope((u + UV_STEP, v), u_scale, hit.tangent());
+                let slope_bitangent = slope((u, v + UV_STEP), v_scale, hit.bitangent());
+                (hit.normal()
+                    - *strength
+                        * (slope_tangent * hit.tangent() + slope_bitangent * hit.bitangent()))
+                .normalized()
+            }
+        }
+    }
+}
+
+/// `hit` with its shading normal bent by `normal_map`, if there is one.
+pub(crate) fn apply_normal_map<'a>(
+    normal_map: &Option<NormalMap>,
+    hit: &'a HitResult,
+) -> Cow<'a, HitResult> {
+    match normal_map {
+        Some(normal_map) => {
+            let normal = normal_map.normal(hit);
+            Cow::Owned(hit.clone().with_shading_normal(normal))
+        }
+        None => Cow::Borrowed(hit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        material::Lambertian, texture::SolidColor, Disk, Hittable, Point3, Quad, Ray,
+        StaticTransform,
+    };
+
+    /// Height that rises by one across the whole of `v`.
+    struct RampV;
+
+    impl Texture for RampV {
+        fn value(&self, (_, v): (f64, f64), _: &Point3) -> Color {
+            Color::one() * v
+        }
+    }
+
+    fn tilt(hit: &HitResult) -> f64 {
+        let bump = NormalMap::Bump {
+            height: Arc::new(RampV),
+            strength: 1.0,
+        };
+        bump.normal(hit).dot(&hit.normal()).acos()
+    }
+
+    #[test]
+    fn bump_slope_is_independent_of_the_shape_and_its_size() {
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        for &size in &[1e-2, 1.0, 1e2] {
+            // Both ramps rise by one over `size`
+            let quad = Quad::from(
+                StaticTransform::new(Point3::zero()),
+                Point3::zero(),
+                Vec3::new(0.0, 0.0, size),
+                Vec3::new(size, 0.0, 0.0),
+                material.clone(),
+            );
+            let disk = Disk::from(StaticTransform::new(Point3::zero()), size, material.clone());
+            let down =
+                |x: f64, z: f64| Ray::new(Point3::new(x, size, z), Vec3::new(0.0, -1.0, 0.0), 0.0);
+            let quad_hit = quad
+                .hit(&down(0.4 * size, 0.3 * size), 0.0, f64::INFINITY)
+                .unwrap();
+            let disk_hit = disk
+                .hit(&down(0.3 * size, 0.4 * size), 0.0, f64::INFINITY)
+                .unwrap();
+
+            let expected = (1.0 / size).atan();
+            assert!((tilt(&quad_hit) - expected).abs() < 1e-6, "size {}", size);
+            assert!((tilt(&disk_hit) - expected).abs() < 1e-6, "size {}", size);
+        }
+    }
+}
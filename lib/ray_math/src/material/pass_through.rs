@@ -0,0 +1,22 @@
+use crate::{Color, Ray};
+
+use super::{Material, ScatterResult};
+
+/// An invisible surface that lets every ray continue unchanged. Useful as the
+/// boundary of a medium that should not refract or reflect, such as a bank of
+/// fog.
+pub struct PassThrough;
+
+impl Material for PassThrough {
+    fn scatter(
+        &self,
+        _rng: &mut dyn rand::RngCore,
+        hit: &crate::HitResult,
+        ray_in: &Ray,
+    ) -> Option<ScatterResult> {
+        Some(ScatterResult {
+            scattered: Ray::new(hit.point(), ray_in.direction(), ray_in.time()),
+            attenuation: Color::one(),
+        })
+    }
+}
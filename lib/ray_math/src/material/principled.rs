@@ -0,0 +1,347 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::{
+    texture::{SolidColor, Texture},
+    Color, HitResult, Ray, Vec3,
+};
+
+use super::{
+    apply_normal_map, fresnel_dielectric, microfacet::Ggx, Material, NormalMap, ScatterResult,
+};
+
+/// Index of refraction of the clearcoat layer, a typical varnish.
+const CLEARCOAT_INDEX_OF_REFRACTION: f64 = 1.5;
+
+/// Parameters of a `Principled` material. Colors are read from their
+/// textures, and every other parameter is the brightness of its texture, in
+/// [0,1] unless noted.
+pub struct PrincipledConfig {
+    /// Diffuse albedo of dielectrics, reflectance of metals and the color of
+    /// light transmitted through glass.
+    pub base_color: Arc<dyn Texture>,
+    /// Blends from a dielectric (0) to a metal (1).
+    pub metallic: Arc<dyn Texture>,
+    /// Perceptual roughness of the specular, metal and glass lobes.
+    pub roughness: Arc<dyn Texture>,
+    /// Strength of the dielectric specular reflection, where 0.5 is a
+    /// reflectance of 4% head on.
+    pub specular: Arc<dyn Texture>,
+    /// Tints the dielectric specular reflection towards the base color.
+    pub specular_tint: Arc<dyn Texture>,
+    /// Soft retroreflection at grazing angles, for cloth.
+    pub sheen: Arc<dyn Texture>,
+    /// Tints the sheen towards the base color.
+    pub sheen_tint: Arc<dyn Texture>,
+    /// Strength of a clear varnish layer on top of everything else.
+    pub clearcoat: Arc<dyn Texture>,
+    /// Perceptual roughness of the clearcoat.
+    pub clearcoat_roughness: Arc<dyn Texture>,
+    /// Blends the dielectric from opaque (0) to glass (1).
+    pub transmission: Arc<dyn Texture>,
+    /// Index of refraction of the glass when transmissive.
+    pub index_of_refraction: f64,
+    /// Light given off, multiplied by `emission_strength`.
+    pub emission: Arc<dyn Texture>,
+    pub emission_strength: f64,
+}
+
+impl Default for PrincipledConfig {
+    /// A light grey, fairly rough dielectric.
+    fn default() -> Self {
+        Self {
+            base_color: uniform(0.8),
+            metallic: uniform(0.0),
+            roughness: uniform(0.5),
+            specular: uniform(0.5),
+            specular_tint: uniform(0.0),
+            sheen: uniform(0.0),
+            sheen_tint: uniform(0.5),
+            clearcoat: uniform(0.0),
+            clearcoat_roughness: uniform(0.03),
+            transmission: uniform(0.0),
+            index_of_refraction: 1.5,
+            emission: uniform(0.0),
+            emission_strength: 1.0,
+        }
+    }
+}
+
+/// A texture with the same brightness everywhere.
+fn uniform(value: f64) -> Arc<dyn Texture> {
+    Arc::new(SolidColor::new(Color::one() * value))
+}
+
+/// An uber material covering most real surfaces with one set of artist
+/// friendly parameters, after Burley's "Physically Based Shading at Disney"
+/// and the metallic-roughness model of glTF, so imported materials can be
+/// mapped onto it directly.
+///
+/// Each scatter picks a single lobe: first the clearcoat reflects in
+/// proportion to its Fresnel reflectance, then the rest of the light goes to a
+/// metal, glass or opaque dielectric lobe in proportion to `metallic` and
+/// `transmission`. The opaque dielectric chooses between its GGX specular
+/// reflection and a diffuse base with sheen by the specular reflectance, so
+/// what isn't reflected is what gets diffused.
+pub struct Principled {
+    config: PrincipledConfig,
+    normal_map: Option<NormalMap>,
+}
+
+/// Parameters looked up at a hit.
+struct Parameters {
+    base_color: Color,
+    metallic: f64,
+    roughness: f64,
+    specular: f64,
+    specular_tint: f64,
+    sheen: f64,
+    sheen_tint: f64,
+    clearcoat: f64,
+    clearcoat_roughness: f64,
+    transmission: f64,
+}
+
+impl Principled {
+    pub fn new(config: PrincipledConfig) -> Self {
+        Self {
+            config,
+            normal_map: None,
+        }
+    }
+
+    pub fn with_normal_map(mut self, normal_map: NormalMap) -> Self {
+        self.normal_map = Some(normal_map);
+        self
+    }
+
+    pub fn config(&self) -> &PrincipledConfig {
+        &self.config
+    }
+
+    fn parameters(&self, hit: &HitResult) -> Parameters {
+        let (uv, point) = (hit.uv(), hit.point());
+        let scalar = |texture: &Arc<dyn Texture>| {
+            let color = texture.value(uv, &point);
+            ((color.x() + color.y() + color.z()) / 3.0).clamp(0.0, 1.0)
+        };
+        Parameters {
+            base_color: self.config.base_color.value(uv, &point) * hit.color(),
+            metallic: scalar(&self.config.metallic),
+            roughness: scalar(&self.config.roughness),
+            specular: scalar(&self.config.specular),
+            specular_tint: scalar(&self.config.specular_tint),
+            sheen: scalar(&self.config.sheen),
+            sheen_tint: scalar(&self.config.sheen_tint),
+            clearcoat: scalar(&self.config.clearcoat),
+            clearcoat_roughness: scalar(&self.config.clearcoat_roughness),
+            transmission: scalar(&self.config.transmission),
+        }
+    }
+}
+
+impl Material for Principled {
+    fn scatter(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        hit: &HitResult,
+        ray_in: &Ray,
+    ) -> Option<ScatterResult> {
+        let hit = apply_normal_map(&self.normal_map, hit);
+        let wo = hit.to_local(&-ray_in.direction().normalized());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+        let parameters = self.parameters(&hit);
+        let scatter = |wi: Vec3, attenuation: Color| {
+            // Discard directions sent to the wrong side of the surface, for
+            // either normal
+            let direction = hit.to_world(&wi);
+            let side = wi.z().signum();
+            if wi.z() == 0.0 || direction.dot(&hit.geometric_normal()) * side <= 0.0 {
+                return None;
+            }
+            Some(ScatterResult {
+                scattered: Ray::new(hit.point(), direction, ray_in.time()),
+                attenuation,
+            })
+        };
+
+        if parameters.clearcoat > 0.0 {
+            let ggx = Ggx::from_roughness(parameters.clearcoat_roughness);
+            let microfacet_normal = ggx.sample_visible_normal(rng, &wo);
+            let reflectance = parameters.clearcoat
+                * fresnel_dielectric(wo.dot(&microfacet_normal), CLEARCOAT_INDEX_OF_REFRACTION);
+            if reflectance > rng.gen_range(0.0..1.0) {
+                let wi = Vec3::reflect(&-wo, &microfacet_normal);
+                if wi.z() <= 0.0 {
+                    return None;
+                }
+                let shadowing = ggx.masking_shadowing(&wo, &wi) / ggx.masking(&wo);
+                return scatter(wi, Color::one() * shadowing);
+            }
+        }
+
+        let ggx = Ggx::from_roughness(parameters.roughness);
+        let lobe = rng.gen_range(0.0..1.0);
+        if lobe < parameters.metallic {
+            // Metal, with Schlick's Fresnel tinted by the base color
+            let microfacet_normal = ggx.sample_visible_normal(rng, &wo);
+            let wi = Vec3::reflect(&-wo, &microfacet_normal);
+            if wi.z() <= 0.0 {
+                return None;
+            }
+            let fresnel = schlick(parameters.base_color, wo.dot(&microfacet_normal));
+            let shadowing = ggx.masking_shadowing(&wo, &wi) / ggx.masking(&wo);
+            return scatter(wi, fresnel * shadowing);
+        }
+
+        if lobe < parameters.metallic + (1.0 - parameters.metallic) * parameters.transmission {
+            // Rough glass, as `RoughDielectric`, coloring what's transmitted
+            let eta = if hit.front_face() {
+                self.config.index_of_refraction
+            } else {
+                1.0 / self.config.index_of_refraction
+            };
+            let microfacet_normal = ggx.sample_visible_normal(rng, &wo);
+            let reflected =
+                fresnel_dielectric(wo.dot(&microfacet_normal), eta) > rng.gen_range(0.0..1.0);
+            let (wi, color) = if reflected {
+                (Vec3::reflect(&-wo, &microfacet_normal), Color::one())
+            } else {
+                (
+                    Vec3::refract(&-wo, &microfacet_normal, 1.0 / eta),
+                    parameters.base_color,
+                )
+            };
+            if (wi.z() > 0.0) != reflected {
+                return None;
+            }
+            let shadowing = ggx.masking_shadowing(&wo, &wi) / ggx.masking(&wo);
+            return scatter(wi, color * shadowing);
+        }
+
+        // Opaque dielectric: the specular reflection, tinted towards the hue
+        // of the base color, or what it lets through to the diffuse base
+        let luminance = parameters
+            .base_color
+            .dot(&Color::new(0.2126, 0.7152, 0.0722));
+        let mut hue = if luminance > 0.0 {
+            parameters.base_color / luminance
+        } else {
+            Color::one()
+        };
+        // Saturated colors have a channel well above their luminance, which
+        // would make the tinted reflectance more than one
+        for channel in 0..3 {
+            hue[channel] = hue[channel].clamp(0.0, 1.0);
+        }
+        let specular_color =
+            0.08 * parameters.specular * Color::lerp(Color::one(), hue, parameters.specular_tint);
+        let microfacet_normal = ggx.sample_visible_normal(rng, &wo);
+        let fresnel = schlick(specular_color, wo.dot(&microfacet_normal));
+        let specular_probability = mean(&fresnel).clamp(0.0, 1.0);
+        if specular_probability > rng.gen_range(0.0..1.0) {
+            let wi = Vec3::reflect(&-wo, &microfacet_normal);
+            if wi.z() <= 0.0 {
+                return None;
+            }
+            let shadowing = ggx.masking_shadowing(&wo, &wi) / ggx.masking(&wo);
+            return scatter(wi, fresnel / specular_probability * shadowing);
+        }
+
+        let mut wi = Vec3::new(0.0, 0.0, 1.0) + Vec3::random_unit(rng);
+        if wi.nearly_zero() {
+            wi = Vec3::new(0.0, 0.0, 1.0);
+        }
+        let wi = wi.normalized();
+        let half_vector = (wi + wo).normalized();
+        let sheen_color = parameters.sheen * Color::lerp(Color::one(), hue, parameters.sheen_tint);
+        let diffuse = (Color::one() - fresnel) * parameters.base_color
+            + sheen_color * (1.0 - wi.dot(&half_vector)).powi(5);
+        scatter(wi, diffuse / (1.0 - specular_probability))
+    }
+
+    fn emitted(&self, hit: &HitResult, _ray_in: &Ray) -> Color {
+        self.config.emission.value(hit.uv(), &hit.point()) * self.config.emission_strength
+    }
+}
+
+/// Schlick's approximation of the Fresnel reflectance, from the reflectance
+/// head on.
+fn schlick(normal_reflectance: Color, cos_theta: f64) -> Color {
+    let weight = (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5);
+    Color::lerp(normal_reflectance, Color::one(), weight)
+}
+
+fn mean(color: &Color) -> f64 {
+    (color.x() + color.y() + color.z()) / 3.0
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{Hittable, Point3, Quad, StaticTransform};
+
+    #[test]
+    fn white_surfaces_never_gain_energy() {
+        let mut rng = StdRng::seed_from_u64(10);
+        let configs = vec![
+            PrincipledConfig {
+                base_color: uniform(1.0),
+                ..Default::default()
+            },
+            PrincipledConfig {
+                base_color: uniform(1.0),
+                roughness: uniform(0.1),
+                specular: uniform(1.0),
+                clearcoat: uniform(1.0),
+                ..Default::default()
+            },
+            PrincipledConfig {
+                base_color: uniform(1.0),
+                metallic: uniform(1.0),
+                roughness: uniform(0.3),
+                ..Default::default()
+            },
+            PrincipledConfig {
+                base_color: uniform(1.0),
+                metallic: uniform(0.5),
+                transmission: uniform(0.5),
+                ..Default::default()
+            },
+        ];
+        for (index, config) in configs.into_iter().enumerate() {
+            let material = Arc::new(Principled::new(config));
+            let quad = Quad::from(
+                StaticTransform::new(Point3::zero()),
+                Point3::new(-1.0, 0.0, -1.0),
+                Vec3::new(0.0, 0.0, 2.0),
+                Vec3::new(2.0, 0.0, 0.0),
+                material.clone(),
+            );
+            for &slant in &[0.0, 1.0, 5.0] {
+                let ray = Ray::new(
+                    Point3::new(-slant, 1.0, 0.0),
+                    Vec3::new(slant, -1.0, 0.0),
+                    0.0,
+                );
+                let hit = quad.hit(&ray, 0.0, f64::INFINITY).unwrap();
+                let samples = 5000;
+                let mut total = Color::zero();
+                for _ in 0..samples {
+                    if let Some(result) = material.scatter(&mut rng, &hit, &ray) {
+                        total += result.attenuation;
+                    }
+                }
+                let mean = total / samples as f64;
+                for channel in 0..3 {
+                    assert!(mean[channel] <= 1.01, "config {}: {}", index, mean[channel]);
+                }
+            }
+        }
+    }
+}
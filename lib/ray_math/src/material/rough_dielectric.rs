@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::{
+    texture::{SolidColor, Texture},
+    Color, HitResult, Ray, Vec3,
+};
+
+use super::{apply_normal_map, microfacet::Ggx, Material, NormalMap, ScatterResult};
+
+/// A dielectric interface made of microfacets following the GGX distribution,
+/// which both reflects and transmits, after Walter et al.'s "Microfacet Models
+/// for Refraction through Rough Surfaces". A microfacet normal is sampled from
+/// those visible from the incoming direction, then the path either reflects
+/// off it or refracts through it in proportion to the exact Fresnel
+/// reflectance there. This gives frosted glass, or with a medium inside,
+/// translucent plastics and wax.
+///
+/// Roughness is the brightness of a texture, from 0 (smooth glass) to 1, and
+/// is squared to give the width of the distribution.
+pub struct RoughDielectric {
+    index_of_refraction: f64,
+    roughness: Arc<dyn Texture>,
+    normal_map: Option<NormalMap>,
+}
+
+impl RoughDielectric {
+    pub fn new(index_of_refraction: f64, roughness: Arc<dyn Texture>) -> Self {
+        Self {
+            index_of_refraction,
+            roughness,
+            normal_map: None,
+        }
+    }
+
+    /// The same roughness everywhere.
+    pub fn uniform(index_of_refraction: f64, roughness: f64) -> Self {
+        Self::new(
+            index_of_refraction,
+            Arc::new(SolidColor::new(Color::one() * roughness)),
+        )
+    }
+
+    pub fn with_normal_map(mut self, normal_map: NormalMap) -> Self {
+        self.normal_map = Some(normal_map);
+        self
+    }
+
+    fn roughness(&self, hit: &HitResult) -> f64 {
+        let color = self.roughness.value(hit.uv(), &hit.point());
+        (color.x() + color.y() + color.z()) / 3.0
+    }
+}
+
+impl Material for RoughDielectric {
+    fn scatter(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        hit: &HitResult,
+        ray_in: &Ray,
+    ) -> Option<ScatterResult> {
+        let hit = apply_normal_map(&self.normal_map, hit);
+        let wo = hit.to_local(&-ray_in.direction().normalized());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        // Relative index of the side being entered over the side left
+        let eta = if hit.front_face() {
+            self.index_of_refraction
+        } else {
+            1.0 / self.index_of_refraction
+        };
+
+        let ggx = Ggx::from_roughness(self.roughness(&hit));
+        let microfacet_normal = ggx.sample_visible_normal(rng, &wo);
+        let cos_theta = wo.dot(&microfacet_normal);
+        let reflected = fresnel_dielectric(cos_theta, eta) > rng.gen_range(0.0..1.0);
+        let wi = if reflected {
+            Vec3::reflect(&-wo, &microfacet_normal)
+        } else {
+            Vec3::refract(&-wo, &microfacet_normal, 1.0 / eta)
+        };
+
+        // Discard directions the microfacet sent to the wrong side of the
+        // surface, for either normal
+        let direction = hit.to_world(&wi);
+        let side = if reflected { 1.0 } else { -1.0 };
+        if wi.z() * side <= 0.0 || direction.dot(&hit.geometric_normal()) * side <= 0.0 {
+            return None;
+        }
+
+        // The Fresnel term is accounted for by choosing between reflection
+        // and transmission with it, leaving the shadowing of `wi`
+        let shadowing = ggx.masking_shadowing(&wo, &wi) / ggx.masking(&wo);
+        Some(ScatterResult {
+            scattered: Ray::new(hit.point(), direction, ray_in.time()),
+            attenuation: Color::one() * shadowing,
+        })
+    }
+}
+
+/// Fresnel reflectance of unpolarized light arriving at `cos_theta_i` to the
+/// normal of an interface between dielectrics, where `eta` is the index of
+/// the side being entered over that of the side left. Total internal
+/// reflection gives 1.
+pub(crate) fn fresnel_dielectric(cos_theta_i: f64, eta: f64) -> f64 {
+    let cos_theta_i = cos_theta_i.clamp(0.0, 1.0);
+    let sin2_theta_t = (1.0 - cos_theta_i * cos_theta_i) / (eta * eta);
+    if sin2_theta_t >= 1.0 {
+        return 1.0;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+    let parallel = (eta * cos_theta_i - cos_theta_t) / (eta * cos_theta_i + cos_theta_t);
+    let perpendicular = (cos_theta_i - eta * cos_theta_t) / (cos_theta_i + eta * cos_theta_t);
+    0.5 * (parallel * parallel + perpendicular * perpendicular)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{Hittable, Point3, Quad, StaticTransform};
+
+    #[test]
+    fn fresnel_limits() {
+        assert!((fresnel_dielectric(1.0, 1.5) - 0.04).abs() < 1e-12);
+        assert!((fresnel_dielectric(1.0, 1.0 / 1.5) - 0.04).abs() < 1e-12);
+        assert_eq!(fresnel_dielectric(0.0, 1.5), 1.0);
+        // Beyond the critical angle leaving glass
+        assert_eq!(fresnel_dielectric(0.5, 1.0 / 1.5), 1.0);
+    }
+
+    #[test]
+    fn smooth_interface_splits_by_fresnel() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let glass = Arc::new(RoughDielectric::uniform(1.5, 0.0));
+        let quad = Quad::from(
+            StaticTransform::new(Point3::zero()),
+            Point3::new(-1.0, 0.0, -1.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            glass.clone(),
+        );
+        let ray = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0);
+        let hit = quad.hit(&ray, 0.0, f64::INFINITY).unwrap();
+
+        let samples = 20_000;
+        let mut reflected = 0;
+        for _ in 0..samples {
+            let result = glass.scatter(&mut rng, &hit, &ray).unwrap();
+            let direction = result.scattered.direction().normalized();
+            assert!(direction.y().abs() > 0.999);
+            assert!(result.attenuation.x() <= 1.0);
+            if direction.y() > 0.0 {
+                reflected += 1;
+            }
+        }
+        let fraction = reflected as f64 / samples as f64;
+        assert!((fraction - 0.04).abs() < 0.01, "{}", fraction);
+    }
+}
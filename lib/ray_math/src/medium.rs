@@ -0,0 +1,190 @@
+use std::{f64, ops::Range, sync::Arc};
+
+use rand::Rng;
+
+use crate::{
+    hittable::perpendicular, spectrum::rgb_to_spectrum, Aabb, Color, HitResult, Hittable, Ray, Vec3,
+};
+
+/// Something filling space that light can be absorbed, scattered or emitted
+/// by on its way between surfaces, such as fog or the inside of a glass of
+/// milk. The renderer keeps a stack of the media a path is currently inside,
+/// entered and left at surfaces carrying a medium interface.
+pub trait Medium: Send + Sync {
+    /// Samples how far `ray` travels through the medium before it scatters,
+    /// looking no further than `t_max` (where the next surface is). If the
+    /// ray carries a wavelength, the sample's weight and emission are the
+    /// values at it, in every channel.
+    fn sample(&self, rng: &mut dyn rand::RngCore, ray: &Ray, t_max: f64) -> MediumSample;
+
+    /// Picks a new direction for a path scattering in the medium while
+    /// travelling along `direction`.
+    fn sample_phase(&self, rng: &mut dyn rand::RngCore, direction: &Vec3) -> Vec3;
+}
+
+pub struct MediumSample {
+    /// Where along the ray the path scattered, or `None` if it made it to
+    /// `t_max` without scattering.
+    pub scatter_t: Option<f64>,
+    /// Path throughput multiplier for the sampled segment.
+    pub weight: Color,
+    /// Light emitted in the medium along the segment and reaching its start,
+    /// already divided by the sampling probability.
+    pub emission: Color,
+}
+
+/// A medium with the same scattering and absorption coefficients everywhere.
+/// The coefficients are per color channel, so light can be tinted by the
+/// distance it travels through the medium.
+pub struct HomogeneousMedium {
+    sigma_a: Color,
+    sigma_s: Color,
+    emission: Color,
+    g: f64,
+}
+
+impl HomogeneousMedium {
+    /// `sigma_a` and `sigma_s` are the absorption and scattering coefficients
+    /// per unit distance.
+    pub fn new(sigma_a: Color, sigma_s: Color) -> Self {
+        Self {
+            sigma_a,
+            sigma_s,
+            emission: Color::zero(),
+            g: 0.0,
+        }
+    }
+
+    /// Radiance emitted per unit of absorption, e.g. for glowing gas.
+    pub fn with_emission(mut self, emission: Color) -> Self {
+        self.emission = emission;
+        self
+    }
+
+    /// Henyey-Greenstein asymmetry in (-1, 1): positive values scatter
+    /// forwards, negative values backwards, and zero is isotropic.
+    pub fn with_asymmetry(mut self, g: f64) -> Self {
+        self.g = g;
+        self
+    }
+
+    /// The absorption and scattering coefficients and emission seen by `ray`.
+    /// A ray carrying a single wavelength sees their values at it in every
+    /// channel, so the attenuation is exponential in the spectral coefficient
+    /// rather than an RGB attenuation turned into a spectrum afterwards.
+    fn coefficients(&self, ray: &Ray) -> (Color, Color, Color) {
+        match ray.wavelength() {
+            Some(wavelength) => {
+                let lift = |rgb: &Color| Color::one() * rgb_to_spectrum(rgb, wavelength);
+                (
+                    lift(&self.sigma_a),
+                    lift(&self.sigma_s),
+                    lift(&self.emission),
+                )
+            }
+            None => (self.sigma_a, self.sigma_s, self.emission),
+        }
+    }
+}
+
+fn transmittance(sigma_t: &Color, distance: f64) -> Color {
+    Color::new(
+        (-sigma_t.x() * distance).exp(),
+        (-sigma_t.y() * distance).exp(),
+        (-sigma_t.z() * distance).exp(),
+    )
+}
+
+impl Medium for HomogeneousMedium {
+    fn sample(&self, rng: &mut dyn rand::RngCore, ray: &Ray, t_max: f64) -> MediumSample {
+        // Sample a distance using one randomly chosen channel's extinction,
+        // weighting by the average density over all channels
+        let (sigma_a, sigma_s, emission) = self.coefficients(ray);
+        let sigma_t = sigma_a + sigma_s;
+        let channel = rng.gen_range(0..3);
+        let ray_length = ray.direction().length();
+        let max_distance = t_max * ray_length;
+
+        // A medium that only absorbs never scatters, so the attenuation along
+        // the whole segment (Beer-Lambert) and what it emits can be computed
+        // exactly instead of sampled
+        if sigma_s.nearly_zero() {
+            let transmittance = transmittance(&sigma_t, max_distance);
+            return MediumSample {
+                scatter_t: None,
+                weight: transmittance,
+                emission: (Color::one() - transmittance) * emission,
+            };
+        }
+
+        let random: f64 = rng.gen_range(f64::EPSILON..=1.0);
+        let distance = -random.ln() / sigma_t[channel];
+
+        if distance < max_distance {
+            let transmittance = transmittance(&sigma_t, distance);
+            let density = sigma_t * transmittance;
+            let pdf = (density.x() + density.y() + density.z()) / 3.0;
+            MediumSample {
+                scatter_t: Some(distance / ray_length),
+                weight: transmittance * sigma_s / pdf,
+                emission: transmittance * sigma_a * emission / pdf,
+            }
+        } else {
+            let transmittance = transmittance(&sigma_t, max_distance);
+            let pdf = (transmittance.x() + transmittance.y() + transmittance.z()) / 3.0;
+            MediumSample {
+                scatter_t: None,
+                weight: transmittance / pdf,
+                emission: Color::zero(),
+            }
+        }
+    }
+
+    fn sample_phase(&self, rng: &mut dyn rand::RngCore, direction: &Vec3) -> Vec3 {
+        if self.g.abs() < 1e-3 {
+            return Vec3::random_unit(rng);
+        }
+
+        // Invert the Henyey-Greenstein CDF for the angle to `direction`
+        let g = self.g;
+        let xi: f64 = rng.gen_range(0.0..1.0);
+        let sqr_term = (1.0 - g * g) / (1.0 + g - 2.0 * g * xi);
+        let cos_theta = (1.0 + g * g - sqr_term * sqr_term) / (2.0 * g);
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * f64::consts::PI * rng.gen_range(0.0..1.0);
+
+        let w = direction.normalized();
+        let u = perpendicular(&w);
+        let v = w.cross(&u);
+        sin_theta * phi.cos() * u + sin_theta * phi.sin() * v + cos_theta * w
+    }
+}
+
+/// Marks the inside of a closed `boundary` as being filled with `interior`.
+/// Paths enter the medium when they are transmitted through the boundary from
+/// outside, and leave it when transmitted back out, so the boundary's own
+/// material (e.g. `Dielectric` or `PassThrough`) still decides what happens at
+/// the surface.
+pub struct MediumBoundary {
+    boundary: Arc<dyn Hittable>,
+    interior: Arc<dyn Medium>,
+}
+
+impl MediumBoundary {
+    pub fn new(boundary: Arc<dyn Hittable>, interior: Arc<dyn Medium>) -> Self {
+        Self { boundary, interior }
+    }
+}
+
+impl Hittable for MediumBoundary {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        self.boundary.hit(ray, t_min, t_max).map(|mut hit| {
+            hit.set_interior_medium(Arc::clone(&self.interior));
+            hit
+        })
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        self.boundary.bounding_box(time_range)
+    }
+}
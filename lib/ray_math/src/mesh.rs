@@ -0,0 +1,295 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    material::Material, triangle::hit_triangle, Aabb, Bvh4, HitResult, Hittable, HittableList,
+    Point3, Ray, Transform, Vec3,
+};
+
+/// Triangle mesh geometry: vertex positions, optional per-vertex normals and
+/// UVs, and triangles as triples of vertex indices. Triangles are wound
+/// counter-clockwise when seen from the outside.
+pub struct Mesh {
+    positions: Vec<Point3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<(f64, f64)>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    /// # Panics
+    ///
+    /// Panics if a triangle refers to a vertex that doesn't exist
+    pub fn new(positions: Vec<Point3>, triangles: Vec<[usize; 3]>) -> Self {
+        assert!(
+            triangles.iter().flatten().all(|&i| i < positions.len()),
+            "Mesh triangle vertex index out of range"
+        );
+        Self {
+            positions,
+            normals: Vec::new(),
+            uvs: Vec::new(),
+            triangles,
+        }
+    }
+
+    /// Sets the normal at each vertex, interpolated across triangles for
+    /// smooth shading.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't one normal per vertex
+    pub fn with_normals(mut self, normals: Vec<Vec3>) -> Self {
+        assert_eq!(
+            normals.len(),
+            self.positions.len(),
+            "Mesh normal count does not match its vertex count"
+        );
+        self.normals = normals.iter().map(Vec3::normalized).collect();
+        self
+    }
+
+    /// Sets vertex normals to the average of the normals of the triangles
+    /// around each vertex, weighted by triangle area.
+    pub fn with_smooth_normals(self) -> Self {
+        let mut normals = vec![Vec3::zero(); self.positions.len()];
+        for &[a, b, c] in &self.triangles {
+            let [pa, pb, pc] = [a, b, c].map(|i| self.positions[i]);
+            // Twice the area, pointing outwards
+            let area_normal = (pb - pa).cross(&(pc - pa));
+            for i in [a, b, c] {
+                normals[i] += area_normal;
+            }
+        }
+        for normal in normals.iter_mut() {
+            if normal.nearly_zero() {
+                *normal = Vec3::new(0.0, 1.0, 0.0);
+            }
+        }
+        self.with_normals(normals)
+    }
+
+    /// Sets the UV coordinates at each vertex.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't one UV per vertex
+    pub fn with_uvs(mut self, uvs: Vec<(f64, f64)>) -> Self {
+        assert_eq!(
+            uvs.len(),
+            self.positions.len(),
+            "Mesh UV count does not match its vertex count"
+        );
+        self.uvs = uvs;
+        self
+    }
+
+    pub fn positions(&self) -> &[Point3] {
+        &self.positions
+    }
+
+    /// Per-vertex normals, or empty if the mesh is flat shaded.
+    pub fn normals(&self) -> &[Vec3] {
+        &self.normals
+    }
+
+    /// Per-vertex UVs, or empty if the mesh has none.
+    pub fn uvs(&self) -> &[(f64, f64)] {
+        &self.uvs
+    }
+
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+}
+
+/// A mesh positioned by a transform, with its triangles in their own bounding
+/// volume hierarchy.
+pub struct MeshObject<T> {
+    transform: T,
+    mesh: Arc<Mesh>,
+    bvh: Bvh4,
+}
+
+impl<T: Transform> MeshObject<T> {
+    /// # Panics
+    ///
+    /// Panics if the mesh has no triangles
+    pub fn from(
+        rng: &mut dyn rand::RngCore,
+        transform: T,
+        mesh: Arc<Mesh>,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        let mut triangles = HittableList::new();
+        for index in 0..mesh.triangles.len() {
+            triangles.add(Arc::new(MeshTriangle {
+                mesh: Arc::clone(&mesh),
+                index,
+                material: Arc::clone(&material),
+            }));
+        }
+
+        Self {
+            transform,
+            mesh,
+            bvh: Bvh4::new(rng, triangles, 0.0..1.0),
+        }
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn mesh(&self) -> &Mesh {
+        &self.mesh
+    }
+}
+
+impl<T: Transform> Hittable for MeshObject<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let position = self.transform.position(ray.time());
+        let local_ray = Ray::new(ray.origin() - position, ray.direction(), ray.time());
+        self.bvh
+            .hit(&local_ray, t_min, t_max)
+            .map(|hit| hit.translated(position))
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        let local = self.bvh.bounding_box(0.0..1.0)?;
+        Some(Aabb::swept(&local, &self.transform, time_range))
+    }
+}
+
+/// One triangle of a mesh, in the mesh's local space.
+struct MeshTriangle {
+    mesh: Arc<Mesh>,
+    index: usize,
+    material: Arc<dyn Material>,
+}
+
+impl MeshTriangle {
+    fn vertices(&self) -> [Point3; 3] {
+        self.mesh.triangles[self.index].map(|i| self.mesh.positions[i])
+    }
+}
+
+impl Hittable for MeshTriangle {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let vertices = self.vertices();
+        let (t, (b1, b2)) = hit_triangle(ray, &vertices, t_min, t_max)?;
+        let [a, b, c] = self.mesh.triangles[self.index];
+        let weights = [1.0 - b1 - b2, b1, b2];
+
+        let edge1 = vertices[1] - vertices[0];
+        let edge2 = vertices[2] - vertices[0];
+        let mut normal = edge1.cross(&edge2).normalized();
+        let shading_normal = if self.mesh.normals.is_empty() {
+            normal
+        } else {
+            let [na, nb, nc] = [a, b, c].map(|i| self.mesh.normals[i]);
+            let shading_normal = weights[0] * na + weights[1] * nb + weights[2] * nc;
+            // Vertex normals are more likely to be right about which side is
+            // outside than the winding order
+            if normal.dot(&shading_normal) < 0.0 {
+                normal = -normal;
+            }
+            shading_normal.normalized()
+        };
+
+        // The tangent follows increasing `u`, found by solving for the
+        // surface derivatives along U and V from the UV differences along the
+        // edges
+        let (uv, dpdu, dpdv) = if self.mesh.uvs.is_empty() {
+            ((b1, b2), edge1, edge2)
+        } else {
+            let [uv0, uv1, uv2] = [a, b, c].map(|i| self.mesh.uvs[i]);
+            let uv = (
+                weights[0] * uv0.0 + weights[1] * uv1.0 + weights[2] * uv2.0,
+                weights[0] * uv0.1 + weights[1] * uv1.1 + weights[2] * uv2.1,
+            );
+            let (du1, dv1) = (uv1.0 - uv0.0, uv1.1 - uv0.1);
+            let (du2, dv2) = (uv2.0 - uv0.0, uv2.1 - uv0.1);
+            let determinant = du1 * dv2 - dv1 * du2;
+            let (dpdu, dpdv) = if determinant.abs() > 1e-12 {
+                (
+                    (dv2 * edge1 - dv1 * edge2) / determinant,
+                    (du1 * edge2 - du2 * edge1) / determinant,
+                )
+            } else {
+                (edge1, edge2)
+            };
+            (uv, dpdu, dpdv)
+        };
+
+        Some(
+            HitResult::new(ray, ray.at(t), normal, t, uv, Arc::clone(&self.material))
+                .with_shading_normal(shading_normal)
+                .with_tangent(dpdu)
+                .with_uv_scale(dpdu.length(), dpdv.length()),
+        )
+    }
+
+    fn bounding_box(&self, _time_range: Range<f64>) -> Option<Aabb> {
+        Some(Aabb::from_points(&self.vertices()).padded())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{material::Lambertian, texture::SolidColor, Color, StaticTransform};
+
+    #[test]
+    fn interpolates_vertex_attributes() {
+        // A 2 by 1 rectangle in the XZ plane facing up, with UVs spanning it
+        // and normals leaning out towards its ends
+        let mesh = Mesh::new(
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(0.0, 0.0, -1.0),
+                Point3::new(2.0, 0.0, -1.0),
+                Point3::new(2.0, 0.0, 0.0),
+            ],
+            vec![[0, 3, 2], [0, 2, 1]],
+        )
+        .with_normals(vec![
+            Vec3::new(-1.0, 1.0, 0.0),
+            Vec3::new(-1.0, 1.0, 0.0),
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(1.0, 1.0, 0.0),
+        ])
+        .with_uvs(vec![(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]);
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        let position = Point3::new(10.0, 0.0, 0.0);
+        let object = MeshObject::from(
+            &mut StdRng::seed_from_u64(12),
+            StaticTransform::new(position),
+            Arc::new(mesh),
+            material,
+        );
+
+        for &(x, z) in &[(0.5, -0.25), (1.5, -0.75)] {
+            let ray = Ray::new(
+                position + Point3::new(x, 1.0, z),
+                Vec3::new(0.0, -1.0, 0.0),
+                0.0,
+            );
+            let hit = object.hit(&ray, 0.001, f64::INFINITY).unwrap();
+            assert!((hit.point() - (position + Point3::new(x, 0.0, z))).length() < 1e-9);
+            assert!((hit.geometric_normal() - Vec3::new(0.0, 1.0, 0.0)).length() < 1e-9);
+            let (u, v) = hit.uv();
+            assert!((u - 0.5 * x).abs() < 1e-9 && (v + z).abs() < 1e-9);
+            assert_eq!(hit.uv_scale(), (2.0, 1.0));
+            // Leaning from one end's normal to the other's, with the tangent
+            // along X as far as it can be
+            let lean = (x - 1.0).atan2(1.0);
+            let normal = hit.normal();
+            assert!((normal.x().atan2(normal.y()) - lean).abs() < 1e-9);
+            let tangent = hit.tangent();
+            assert!(tangent.dot(&normal).abs() < 1e-9 && tangent.z().abs() < 1e-9);
+            assert!(tangent.x() > 0.0);
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use std::{f64, ops::Range, sync::Arc};
+
+use crate::{
+    disk::azimuth_tangent, material::Material, solve_quadratic, sphere::sphere_uv, Aabb, HitResult,
+    Hittable, Point3, Ray, Transform, Vec3,
+};
+
+/// Subdivisions of the ray before giving up on separating roots.
+const MAX_DEPTH: u32 = 48;
+/// Bisection steps when refining a root.
+const REFINE_STEPS: u32 = 60;
+
+/// One source of a `Metaballs` field. Its contribution falls smoothly from
+/// `weight` at `center` to nothing at `radius`, following Wyvill's kernel
+/// `weight * (1 - r²/radius²)³`. A negative weight carves into the others.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldSource {
+    pub center: Point3,
+    pub radius: f64,
+    pub weight: f64,
+}
+
+impl FieldSource {
+    pub fn new(center: Point3, radius: f64, weight: f64) -> Self {
+        Self {
+            center,
+            radius,
+            weight,
+        }
+    }
+}
+
+/// A blobby surface where the summed field of its sources equals
+/// `threshold`, relative to the transform's position. Sources close to each
+/// other merge smoothly, like drops of liquid.
+///
+/// Along a ray, each source's field is a polynomial in `t`, so intervals of
+/// `t` can be given conservative bounds on the field and on its derivative.
+/// Intervals whose bounds exclude the threshold are skipped, the others split
+/// until the field is monotonic over them, and a root is then refined by
+/// bisection. Normals come from the field's analytic gradient.
+pub struct Metaballs<T> {
+    transform: T,
+    sources: Vec<FieldSource>,
+    threshold: f64,
+    bounds: Aabb,
+    material: Arc<dyn Material>,
+}
+
+/// The part of the ray a source reaches, with the ray relative to its center.
+struct ActiveSource {
+    source: FieldSource,
+    origin: Vec3,
+    enter: f64,
+    exit: f64,
+}
+
+impl<T: Transform> Metaballs<T> {
+    /// # Panics
+    ///
+    /// Panics if there are no sources with a positive weight and radius, or
+    /// `threshold` isn't positive
+    pub fn from(
+        transform: T,
+        sources: Vec<FieldSource>,
+        threshold: f64,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        assert!(threshold > 0.0, "Metaball threshold must be positive");
+
+        // Only sources adding to the field can make the surface
+        let bounds = sources
+            .iter()
+            .filter(|source| source.weight > 0.0 && source.radius > 0.0)
+            .map(|source| {
+                let extent = Vec3::one() * source.radius;
+                Aabb::new(source.center - extent, source.center + extent)
+            })
+            .reduce(|a, b| Aabb::surround(&a, &b))
+            .expect("Metaballs need a source with a positive weight and radius");
+
+        Self {
+            transform,
+            sources,
+            threshold,
+            bounds,
+            material,
+        }
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn sources(&self) -> &[FieldSource] {
+        &self.sources
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// The field minus the threshold at `t`, positive inside the surface.
+    fn field(&self, active: &[ActiveSource], direction: &Vec3, t: f64) -> f64 {
+        active
+            .iter()
+            .map(|active| {
+                let offset = active.origin + t * *direction;
+                let falloff = 1.0 - offset.length_squared() / (active.source.radius.powi(2));
+                if falloff > 0.0 {
+                    active.source.weight * falloff.powi(3)
+                } else {
+                    0.0
+                }
+            })
+            .sum::<f64>()
+            - self.threshold
+    }
+
+    /// Bounds on the field minus the threshold and on its derivative along
+    /// the ray over `[t0, t1]`.
+    fn bound(
+        &self,
+        active: &[ActiveSource],
+        direction: &Vec3,
+        t0: f64,
+        t1: f64,
+    ) -> ((f64, f64), (f64, f64)) {
+        let dd = direction.length_squared();
+        let mut field = (-self.threshold, -self.threshold);
+        let mut slope = (0.0, 0.0);
+        for active in active {
+            if active.exit < t0 || t1 < active.enter {
+                continue;
+            }
+
+            // Squared distance to the center is a parabola in t
+            let od = active.origin.dot(direction);
+            let oo = active.origin.length_squared();
+            let distance_squared = |t: f64| oo + 2.0 * od * t + dd * t * t;
+            let closest = (-od / dd).clamp(t0, t1);
+            let low = distance_squared(closest);
+            let high = distance_squared(t0).max(distance_squared(t1));
+
+            // falloff = 1 - r²/R², clamped where the kernel is cut off
+            let inv_r2 = 1.0 / active.source.radius.powi(2);
+            let falloff = (
+                (1.0 - high * inv_r2).max(0.0),
+                (1.0 - low * inv_r2).max(0.0),
+            );
+            let weight = active.source.weight;
+            field = add(field, scale((falloff.0.powi(3), falloff.1.powi(3)), weight));
+
+            // d/dt = weight * 3 falloff² * -(2 od + 2 dd t) / R²
+            let rate = (
+                -(2.0 * od + 2.0 * dd * t1) * inv_r2,
+                -(2.0 * od + 2.0 * dd * t0) * inv_r2,
+            );
+            let falloff_squared = (falloff.0 * falloff.0, falloff.1 * falloff.1);
+            slope = add(slope, scale(multiply(falloff_squared, rate), 3.0 * weight));
+        }
+        (field, slope)
+    }
+
+    /// Finds the first root of the field over `[t0, t1]`, where it is `f0`
+    /// and `f1` at the ends.
+    fn search(
+        &self,
+        active: &[ActiveSource],
+        direction: &Vec3,
+        (t0, f0): (f64, f64),
+        (t1, f1): (f64, f64),
+        depth: u32,
+    ) -> Option<f64> {
+        let (field, slope) = self.bound(active, direction, t0, t1);
+        if field.0 > 0.0 || field.1 < 0.0 {
+            return None;
+        }
+
+        // Monotonic, so there's a root exactly when the ends differ in sign
+        if slope.0 > 0.0 || slope.1 < 0.0 || depth == MAX_DEPTH {
+            if (f0 > 0.0) == (f1 > 0.0) {
+                return None;
+            }
+            return Some(self.refine(active, direction, (t0, f0), t1));
+        }
+
+        let mid = 0.5 * (t0 + t1);
+        let f_mid = self.field(active, direction, mid);
+        self.search(active, direction, (t0, f0), (mid, f_mid), depth + 1)
+            .or_else(|| self.search(active, direction, (mid, f_mid), (t1, f1), depth + 1))
+    }
+
+    /// Bisects towards the sign change between `t0` and `t1`.
+    fn refine(
+        &self,
+        active: &[ActiveSource],
+        direction: &Vec3,
+        (mut t0, f0): (f64, f64),
+        mut t1: f64,
+    ) -> f64 {
+        let inside = f0 > 0.0;
+        for _ in 0..REFINE_STEPS {
+            let mid = 0.5 * (t0 + t1);
+            if (self.field(active, direction, mid) > 0.0) == inside {
+                t0 = mid;
+            } else {
+                t1 = mid;
+            }
+            if t1 - t0 < 1e-12 {
+                break;
+            }
+        }
+        0.5 * (t0 + t1)
+    }
+
+    /// Gradient of the field at `local`, relative to the transform's position.
+    fn gradient(&self, local: &Point3) -> Vec3 {
+        self.sources
+            .iter()
+            .map(|source| {
+                let offset = *local - source.center;
+                let inv_r2 = 1.0 / (source.radius * source.radius);
+                let falloff = 1.0 - offset.length_squared() * inv_r2;
+                if falloff > 0.0 {
+                    -6.0 * source.weight * falloff * falloff * inv_r2 * offset
+                } else {
+                    Vec3::zero()
+                }
+            })
+            .fold(Vec3::zero(), |a, b| a + b)
+    }
+
+    /// Radius of the sphere the surface near `local` resembles, for the
+    /// distance moved per unit of the normal's UV: the distance to the
+    /// centers of the sources, weighted by how much each adds to the field
+    /// there. Exact for a lone source.
+    fn uv_radius(&self, local: &Point3) -> f64 {
+        let (center, total) = self
+            .sources
+            .iter()
+            .filter(|source| source.weight > 0.0)
+            .map(|source| {
+                let falloff =
+                    1.0 - (*local - source.center).length_squared() / (source.radius.powi(2));
+                let contribution = source.weight * falloff.max(0.0).powi(3);
+                (contribution * source.center, contribution)
+            })
+            .fold((Vec3::zero(), 0.0), |a, b| (a.0 + b.0, a.1 + b.1));
+        if total > 0.0 {
+            (*local - center / total).length()
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: Transform> Hittable for Metaballs<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let position = self.transform.position(ray.time());
+        let origin = ray.origin() - position;
+        let direction = ray.direction();
+        let local_ray = Ray::new(origin, direction, ray.time());
+        let (start, end) = self.bounds.intersect(&local_ray, t_min, t_max)?;
+
+        // Only sources the ray passes within reach of matter
+        let active: Vec<ActiveSource> = self
+            .sources
+            .iter()
+            .filter_map(|source| {
+                let offset = origin - source.center;
+                let (enter, exit) = solve_quadratic(
+                    direction.length_squared(),
+                    2.0 * offset.dot(&direction),
+                    offset.length_squared() - source.radius * source.radius,
+                )?;
+                (exit >= start && enter <= end).then_some(ActiveSource {
+                    source: *source,
+                    origin: offset,
+                    enter,
+                    exit,
+                })
+            })
+            .collect();
+        if active.is_empty() {
+            return None;
+        }
+
+        let ends = [start, end].map(|t| (t, self.field(&active, &direction, t)));
+        let t = self.search(&active, &direction, ends[0], ends[1], 0)?;
+
+        let local = local_ray.at(t);
+        let gradient = self.gradient(&local);
+        // The field falls off outwards
+        let outward_normal = if gradient.nearly_zero() {
+            -direction.normalized()
+        } else {
+            -gradient.normalized()
+        };
+        let radius = self.uv_radius(&local);
+        Some(
+            HitResult::new(
+                ray,
+                ray.at(t),
+                outward_normal,
+                t,
+                sphere_uv(&outward_normal),
+                Arc::clone(&self.material),
+            )
+            .with_tangent(azimuth_tangent(&outward_normal))
+            .with_uv_scale(
+                2.0 * f64::consts::PI * radius * azimuth_tangent(&outward_normal).length(),
+                f64::consts::PI * radius,
+            ),
+        )
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        Some(Aabb::swept(&self.bounds, &self.transform, time_range))
+    }
+}
+
+fn add(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn scale(a: (f64, f64), factor: f64) -> (f64, f64) {
+    if factor >= 0.0 {
+        (a.0 * factor, a.1 * factor)
+    } else {
+        (a.1 * factor, a.0 * factor)
+    }
+}
+
+fn multiply(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let products = [a.0 * b.0, a.0 * b.1, a.1 * b.0, a.1 * b.1];
+    (
+        products.iter().copied().fold(f64::INFINITY, f64::min),
+        products.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{material::Lambertian, texture::SolidColor, Color, Sphere, StaticTransform};
+
+    #[test]
+    fn lone_source_is_a_sphere() {
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        let center = Point3::new(0.5, 1.0, -0.5);
+        let (radius, weight, threshold) = (2.0, 1.5, 0.5);
+        let metaballs = Metaballs::from(
+            StaticTransform::new(Point3::zero()),
+            vec![FieldSource::new(center, radius, weight)],
+            threshold,
+            material.clone(),
+        );
+        // Where weight * (1 - r²/radius²)³ = threshold
+        let surface_radius = radius * (1.0 - (threshold / weight).cbrt()).sqrt();
+        let sphere = Sphere::from(StaticTransform::new(center), surface_radius, material);
+
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut hits = 0;
+        for _ in 0..1000 {
+            // Aimed around the surface, so about half the rays hit it
+            let origin = center + 4.0 * Vec3::random_unit(&mut rng);
+            let target = center + 1.5 * Vec3::random_in_unit_sphere(&mut rng);
+            let ray = Ray::new(origin, target - origin, 0.0);
+            let expected = sphere.hit(&ray, 0.001, f64::INFINITY);
+            let actual = metaballs.hit(&ray, 0.001, f64::INFINITY);
+            match (expected, actual) {
+                (Some(expected), Some(actual)) => {
+                    assert!((expected.t() - actual.t()).abs() < 1e-6);
+                    assert!((expected.normal() - actual.normal()).length() < 1e-6);
+                    assert!((expected.uv_scale().1 - actual.uv_scale().1).abs() < 1e-6);
+                    hits += 1;
+                }
+                (None, None) => {}
+                (expected, actual) => panic!(
+                    "{:?} != {:?}",
+                    expected.map(|hit| hit.t()),
+                    actual.map(|hit| hit.t())
+                ),
+            }
+        }
+        assert!(hits > 100);
+    }
+}
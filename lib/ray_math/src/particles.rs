@@ -0,0 +1,375 @@
+use std::{f64, ops::Range, sync::Arc};
+
+use crate::{
+    disk::azimuth_tangent, material::Material, sphere::sphere_uv, Aabb, Color, HitResult, Hittable,
+    Point3, Ray, Transform, Vec3,
+};
+
+/// Most particles a leaf of the hierarchy holds.
+const LEAF_SIZE: usize = 4;
+
+/// Many spheres sharing one material, relative to the transform's position,
+/// such as the output of a particle simulation. Particles are kept in flat
+/// arrays and found through a bounding volume hierarchy built over them
+/// directly, which is far smaller than a `Sphere` and a BVH leaf per particle.
+///
+/// Particles can have their own colors, which are multiplied into the
+/// material's albedo, and their own velocities for motion blur: a particle is
+/// at its position plus its velocity times the ray's time.
+pub struct ParticleSystem<T> {
+    transform: T,
+    positions: Vec<Point3>,
+    radii: Vec<f64>,
+    colors: Vec<Color>,
+    velocities: Vec<Vec3>,
+    /// Times the hierarchy bounds the particles' motion over.
+    time_range: Range<f64>,
+    /// Particle indices, ordered so each leaf's particles are contiguous.
+    order: Vec<u32>,
+    nodes: Vec<Node>,
+    material: Arc<dyn Material>,
+}
+
+/// A node of the hierarchy. Interior nodes have their first child right after
+/// them and their second at `offset`; leaves hold `count` particles starting
+/// at `offset` in `order`.
+struct Node {
+    bounds: Aabb,
+    offset: u32,
+    count: u32,
+    axis: u8,
+}
+
+impl<T: Transform> ParticleSystem<T> {
+    /// # Panics
+    ///
+    /// Panics if there are no particles, or not one radius per particle
+    pub fn from(
+        transform: T,
+        positions: Vec<Point3>,
+        radii: Vec<f64>,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        assert!(!positions.is_empty(), "A particle system needs particles");
+        assert_eq!(
+            radii.len(),
+            positions.len(),
+            "Particle radius count does not match the particle count"
+        );
+
+        let mut particles = Self {
+            transform,
+            positions,
+            radii,
+            colors: Vec::new(),
+            velocities: Vec::new(),
+            time_range: 0.0..1.0,
+            order: Vec::new(),
+            nodes: Vec::new(),
+            material,
+        };
+        particles.build();
+        particles
+    }
+
+    /// Sets the color of each particle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't one color per particle
+    pub fn with_colors(mut self, colors: Vec<Color>) -> Self {
+        assert_eq!(
+            colors.len(),
+            self.positions.len(),
+            "Particle color count does not match the particle count"
+        );
+        self.colors = colors;
+        self
+    }
+
+    /// Sets the velocity of each particle, in distance per unit of time, for
+    /// rays with times in `time_range` (the camera's shutter interval).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't one velocity per particle
+    pub fn with_velocities(mut self, velocities: Vec<Vec3>, time_range: Range<f64>) -> Self {
+        assert_eq!(
+            velocities.len(),
+            self.positions.len(),
+            "Particle velocity count does not match the particle count"
+        );
+        self.velocities = velocities;
+        self.time_range = time_range;
+        self.build();
+        self
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    fn center(&self, index: usize, time: f64) -> Point3 {
+        match self.velocities.get(index) {
+            Some(velocity) => self.positions[index] + time * *velocity,
+            None => self.positions[index],
+        }
+    }
+
+    /// Bounds of a particle over the times the hierarchy is built for.
+    fn particle_bounds(&self, index: usize) -> Aabb {
+        let offset = Vec3::one() * self.radii[index];
+        let start = self.center(index, self.time_range.start);
+        let end = self.center(index, self.time_range.end);
+        Aabb::surround(
+            &Aabb::new(start - offset, start + offset),
+            &Aabb::new(end - offset, end + offset),
+        )
+    }
+
+    /// Builds the hierarchy over the particles' motion during `time_range`,
+    /// splitting at the median along the axis their centers spread the most.
+    fn build(&mut self) {
+        let count = self.positions.len();
+        let bounds: Vec<Aabb> = (0..count).map(|i| self.particle_bounds(i)).collect();
+        let centroids: Vec<Point3> = bounds
+            .iter()
+            .map(|bounds| 0.5 * (bounds.min() + bounds.max()))
+            .collect();
+
+        let mut order: Vec<u32> = (0..count as u32).collect();
+        let mut nodes = Vec::with_capacity(2 * count / LEAF_SIZE + 1);
+        let mut pending = vec![(0, count, None)];
+        while let Some((start, end, parent)) = pending.pop() {
+            let index = nodes.len();
+            if let Some(parent) = parent {
+                // The second child of `parent`: the first follows it directly
+                let parent: &mut Node = &mut nodes[parent];
+                parent.offset = index as u32;
+            }
+
+            let items = &mut order[start..end];
+            let node_bounds = items
+                .iter()
+                .map(|&i| bounds[i as usize].clone())
+                .reduce(|a, b| Aabb::surround(&a, &b))
+                .unwrap();
+            if items.len() <= LEAF_SIZE {
+                nodes.push(Node {
+                    bounds: node_bounds,
+                    offset: start as u32,
+                    count: items.len() as u32,
+                    axis: 0,
+                });
+                continue;
+            }
+
+            let (low, high) = items.iter().fold(
+                (
+                    Point3::one() * f64::INFINITY,
+                    Point3::one() * f64::NEG_INFINITY,
+                ),
+                |(low, high), &i| {
+                    let c = centroids[i as usize];
+                    (
+                        Point3::new(low.x().min(c.x()), low.y().min(c.y()), low.z().min(c.z())),
+                        Point3::new(
+                            high.x().max(c.x()),
+                            high.y().max(c.y()),
+                            high.z().max(c.z()),
+                        ),
+                    )
+                },
+            );
+            let extent = high - low;
+            let axis = (0..3)
+                .max_by(|&a, &b| extent[a].total_cmp(&extent[b]))
+                .unwrap();
+            let mid = items.len() / 2;
+            items.select_nth_unstable_by(mid, |&a, &b| {
+                centroids[a as usize][axis].total_cmp(&centroids[b as usize][axis])
+            });
+
+            nodes.push(Node {
+                bounds: node_bounds,
+                offset: 0,
+                count: 0,
+                axis: axis as u8,
+            });
+            // Pushed second so the first child is built next, right after
+            pending.push((start + mid, end, Some(index)));
+            pending.push((start, start + mid, None));
+        }
+
+        self.order = order;
+        self.nodes = nodes;
+    }
+
+    fn hit_particle(&self, index: usize, ray: &Ray, t_min: f64, t_max: f64) -> Option<f64> {
+        let oc = ray.origin() - self.center(index, ray.time());
+        let radius = self.radii[index];
+        let a = ray.direction().length_squared();
+        let half_b = oc.dot(&ray.direction());
+        let c = oc.length_squared() - radius * radius;
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let sqrtd = discriminant.sqrt();
+        [(-half_b - sqrtd) / a, (-half_b + sqrtd) / a]
+            .iter()
+            .copied()
+            .find(|&t| t_min <= t && t <= t_max)
+    }
+}
+
+impl<T: Transform> Hittable for ParticleSystem<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, mut t_max: f64) -> Option<HitResult> {
+        let position = self.transform.position(ray.time());
+        let local_ray = Ray::new(ray.origin() - position, ray.direction(), ray.time());
+
+        let mut closest = None;
+        let mut stack = Vec::with_capacity(64);
+        stack.push(0);
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.hit(&local_ray, t_min, t_max) {
+                continue;
+            }
+
+            if node.count > 0 {
+                let start = node.offset as usize;
+                for &particle in &self.order[start..start + node.count as usize] {
+                    let particle = particle as usize;
+                    if let Some(t) = self.hit_particle(particle, &local_ray, t_min, t_max) {
+                        t_max = t;
+                        closest = Some((particle, t));
+                    }
+                }
+            } else if ray.direction()[node.axis as usize] < 0.0 {
+                // Visit the nearer child first so farther ones can be culled
+                stack.push(index + 1);
+                stack.push(node.offset as usize);
+            } else {
+                stack.push(node.offset as usize);
+                stack.push(index + 1);
+            }
+        }
+
+        let (particle, t) = closest?;
+        let point = ray.at(t);
+        let center = self.center(particle, ray.time()) + position;
+        let radius = self.radii[particle];
+        let outward_normal = (point - center) / radius;
+        let hit = HitResult::new(
+            ray,
+            point,
+            outward_normal,
+            t,
+            sphere_uv(&outward_normal),
+            Arc::clone(&self.material),
+        )
+        .with_tangent(azimuth_tangent(&outward_normal))
+        .with_uv_scale(
+            2.0 * f64::consts::PI * radius * azimuth_tangent(&outward_normal).length(),
+            f64::consts::PI * radius,
+        );
+        Some(match self.colors.get(particle) {
+            Some(&color) => hit.with_color(color),
+            None => hit,
+        })
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        Some(Aabb::swept(
+            &self.nodes[0].bounds,
+            &self.transform,
+            time_range,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{material::Lambertian, solve_quadratic, texture::SolidColor, StaticTransform};
+
+    /// The nearest hit of `ray` on any of the particles, testing every one.
+    fn brute_force(
+        positions: &[Point3],
+        velocities: &[Vec3],
+        radii: &[f64],
+        ray: &Ray,
+    ) -> Option<f64> {
+        (0..positions.len())
+            .filter_map(|i| {
+                let oc = ray.origin() - (positions[i] + ray.time() * velocities[i]);
+                let (near, far) = solve_quadratic(
+                    ray.direction().length_squared(),
+                    2.0 * oc.dot(&ray.direction()),
+                    oc.length_squared() - radii[i] * radii[i],
+                )?;
+                [near, far].iter().copied().find(|&t| t >= 0.001)
+            })
+            .reduce(f64::min)
+    }
+
+    #[test]
+    fn hits_match_brute_force_over_the_shutter() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let count = 300;
+        let positions: Vec<Point3> = (0..count)
+            .map(|_| Point3::random(&mut rng, -4.0, 4.0))
+            .collect();
+        let velocities: Vec<Vec3> = (0..count)
+            .map(|_| Vec3::random(&mut rng, -1.0, 1.0))
+            .collect();
+        let radii: Vec<f64> = (0..count).map(|_| rng.gen_range(0.05..0.3)).collect();
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        // A shutter outside [0,1], where particles have moved well away from
+        // where they are at those times
+        let shutter = 4.0..5.0;
+        let particles = ParticleSystem::from(
+            StaticTransform::new(Point3::zero()),
+            positions.clone(),
+            radii.clone(),
+            material,
+        )
+        .with_velocities(velocities.clone(), shutter.clone());
+
+        let mut hits = 0;
+        for _ in 0..3000 {
+            let origin = Point3::random(&mut rng, -10.0, 10.0);
+            let target = Point3::random(&mut rng, -6.0, 6.0);
+            let time = rng.gen_range(shutter.clone());
+            let ray = Ray::new(origin, target - origin, time);
+            let expected = brute_force(&positions, &velocities, &radii, &ray);
+            let actual = particles.hit(&ray, 0.001, f64::INFINITY).map(|hit| hit.t());
+            match (expected, actual) {
+                (Some(expected), Some(actual)) => {
+                    assert!(
+                        (expected - actual).abs() < 1e-9,
+                        "{} != {}",
+                        expected,
+                        actual
+                    );
+                    hits += 1;
+                }
+                (None, None) => {}
+                _ => panic!("{:?} != {:?}", expected, actual),
+            }
+        }
+        assert!(hits > 100);
+    }
+}
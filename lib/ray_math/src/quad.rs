@@ -0,0 +1,107 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{material::Material, Aabb, HitResult, Hittable, Point3, Ray, Transform, Vec3};
+
+/// A flat parallelogram with one corner at `corner` (relative to the
+/// transform's position) and sides along `u` and `v`.
+pub struct Quad<T> {
+    transform: T,
+    corner: Point3,
+    u: Vec3,
+    v: Vec3,
+    normal: Vec3,
+    /// `n / (n . n)` for the unnormalized normal `n`, used to find the planar
+    /// coordinates of hit points
+    w: Vec3,
+    material: Arc<dyn Material>,
+}
+
+impl<T: Transform> Quad<T> {
+    pub fn from(
+        transform: T,
+        corner: Point3,
+        u: Vec3,
+        v: Vec3,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        let n = u.cross(&v);
+        Self {
+            transform,
+            corner,
+            u,
+            v,
+            normal: n.normalized(),
+            w: n / n.length_squared(),
+            material,
+        }
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn corner(&self) -> Point3 {
+        self.corner
+    }
+
+    pub fn u(&self) -> Vec3 {
+        self.u
+    }
+
+    pub fn v(&self) -> Vec3 {
+        self.v
+    }
+
+    pub fn normal(&self) -> Vec3 {
+        self.normal
+    }
+}
+
+impl<T: Transform> Hittable for Quad<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let corner = self.transform.position(ray.time()) + self.corner;
+        let denominator = self.normal.dot(&ray.direction());
+        // Parallel to the plane
+        if denominator.abs() < 1e-8 {
+            return None;
+        }
+
+        let t = (self.normal.dot(&corner) - self.normal.dot(&ray.origin())) / denominator;
+        if t < t_min || t_max < t {
+            return None;
+        }
+
+        // Planar coordinates of the hit point along `u` and `v`
+        let point = ray.at(t);
+        let planar = point - corner;
+        let alpha = self.w.dot(&planar.cross(&self.v));
+        let beta = self.w.dot(&self.u.cross(&planar));
+        if !(0.0..=1.0).contains(&alpha) || !(0.0..=1.0).contains(&beta) {
+            return None;
+        }
+
+        Some(
+            HitResult::new(
+                ray,
+                point,
+                self.normal,
+                t,
+                (alpha, beta),
+                Arc::clone(&self.material),
+            )
+            .with_tangent(self.u)
+            .with_uv_scale(self.u.length(), self.v.length()),
+        )
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        let local = Aabb::from_points(&[
+            self.corner,
+            self.corner + self.u,
+            self.corner + self.v,
+            self.corner + self.u + self.v,
+        ])
+        .padded();
+        Some(Aabb::swept(&local, &self.transform, time_range))
+    }
+}
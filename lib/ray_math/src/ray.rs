@@ -1,9 +1,11 @@
 use crate::vec3::{Point3, Vec3};
 
+#[derive(Clone, Copy)]
 pub struct Ray {
     origin: Point3,
     direction: Vec3,
     time: f64,
+    wavelength: Option<f64>,
 }
 
 impl Ray {
@@ -12,9 +14,17 @@ impl Ray {
             origin,
             direction,
             time,
+            wavelength: None,
         }
     }
 
+    /// The same ray carrying light of a single wavelength in nanometers,
+    /// for paths through dispersive materials.
+    pub fn with_wavelength(mut self, wavelength: f64) -> Self {
+        self.wavelength = Some(wavelength);
+        self
+    }
+
     pub fn origin(&self) -> Point3 {
         self.origin
     }
@@ -27,6 +37,12 @@ impl Ray {
         self.time
     }
 
+    /// The wavelength in nanometers the ray carries, or `None` if it carries
+    /// all of them as RGB.
+    pub fn wavelength(&self) -> Option<f64> {
+        self.wavelength
+    }
+
     pub fn at(&self, t: f64) -> Vec3 {
         self.origin + self.direction * t
     }
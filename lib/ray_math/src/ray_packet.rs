@@ -0,0 +1,72 @@
+use crate::Ray;
+
+/// Number of rays traced together in a `RayPacket`.
+pub const PACKET_WIDTH: usize = 4;
+
+/// One value per ray in a packet.
+pub type Lanes = [f64; PACKET_WIDTH];
+/// Which rays in a packet are still being traced.
+pub type LaneMask = [bool; PACKET_WIDTH];
+
+/// A group of coherent rays (e.g. camera rays for neighbouring samples) stored
+/// in a structure-of-arrays layout so that per-lane arithmetic compiles down to
+/// straight-line code the optimizer can vectorize.
+pub struct RayPacket {
+    rays: [Ray; PACKET_WIDTH],
+    origin: [Lanes; 3],
+    direction: [Lanes; 3],
+    inv_direction: [Lanes; 3],
+    time: Lanes,
+}
+
+impl RayPacket {
+    pub fn new(rays: [Ray; PACKET_WIDTH]) -> Self {
+        let mut origin = [[0.0; PACKET_WIDTH]; 3];
+        let mut direction = [[0.0; PACKET_WIDTH]; 3];
+        let mut inv_direction = [[0.0; PACKET_WIDTH]; 3];
+        let mut time = [0.0; PACKET_WIDTH];
+        for (lane, ray) in rays.iter().enumerate() {
+            for axis in 0..3 {
+                origin[axis][lane] = ray.origin()[axis];
+                direction[axis][lane] = ray.direction()[axis];
+                inv_direction[axis][lane] = 1.0 / ray.direction()[axis];
+            }
+            time[lane] = ray.time();
+        }
+
+        Self {
+            rays,
+            origin,
+            direction,
+            inv_direction,
+            time,
+        }
+    }
+
+    pub fn rays(&self) -> &[Ray; PACKET_WIDTH] {
+        &self.rays
+    }
+
+    pub fn ray(&self, lane: usize) -> &Ray {
+        &self.rays[lane]
+    }
+
+    /// Origin component along `axis` for every lane.
+    pub fn origin(&self, axis: usize) -> &Lanes {
+        &self.origin[axis]
+    }
+
+    /// Direction component along `axis` for every lane.
+    pub fn direction(&self, axis: usize) -> &Lanes {
+        &self.direction[axis]
+    }
+
+    /// Reciprocal of the direction component along `axis` for every lane.
+    pub fn inv_direction(&self, axis: usize) -> &Lanes {
+        &self.inv_direction[axis]
+    }
+
+    pub fn time(&self) -> &Lanes {
+        &self.time
+    }
+}
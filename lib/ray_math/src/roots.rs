@@ -0,0 +1,352 @@
+use std::{f64, ops::Deref};
+
+/// Coefficients this close to zero are treated as zero.
+const EPSILON: f64 = 1e-9;
+
+/// Real roots of a polynomial of degree at most four, in ascending order.
+#[derive(Debug, Clone, Copy)]
+pub struct Roots {
+    values: [f64; 4],
+    count: usize,
+}
+
+impl Roots {
+    fn new() -> Self {
+        Self {
+            values: [0.0; 4],
+            count: 0,
+        }
+    }
+
+    fn push(&mut self, root: f64) {
+        self.values[self.count] = root;
+        self.count += 1;
+    }
+
+    /// Sorts the roots, dropping any that degenerate coefficients made NaN or
+    /// infinite.
+    fn sorted(self) -> Self {
+        let mut sorted = Self::new();
+        for &root in self.values[..self.count].iter() {
+            if root.is_finite() {
+                sorted.push(root);
+            }
+        }
+        sorted.values[..sorted.count].sort_by(f64::total_cmp);
+        sorted
+    }
+}
+
+impl Deref for Roots {
+    type Target = [f64];
+
+    fn deref(&self) -> &[f64] {
+        &self.values[..self.count]
+    }
+}
+
+/// Real roots of `a*x^2 + b*x + c`, smallest first. Uses the form that avoids
+/// cancellation between `-b` and the square root of the discriminant.
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
+    if a == 0.0 {
+        if b == 0.0 {
+            return None;
+        }
+        let root = -c / b;
+        return Some((root, root));
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+    let (r0, r1) = if q == 0.0 { (0.0, 0.0) } else { (q / a, c / q) };
+    Some(if r0 <= r1 { (r0, r1) } else { (r1, r0) })
+}
+
+/// Real roots of `a*x^3 + b*x^2 + c*x + d`, using Cardano's method with the
+/// trigonometric form when there are three real roots. Repeated roots are only
+/// reported once.
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Roots {
+    let mut roots = Roots::new();
+    if a.abs() < EPSILON {
+        if let Some((r0, r1)) = solve_quadratic(b, c, d) {
+            roots.push(r0);
+            if r1 != r0 {
+                roots.push(r1);
+            }
+        }
+        return roots;
+    }
+
+    // Normal form x^3 + A x^2 + B x + C, then substitute x = y - A/3 to get
+    // the depressed cubic y^3 + 3p y + 2q
+    let (a, b, c) = (b / a, c / a, d / a);
+    let sq_a = a * a;
+    let p = (-sq_a / 3.0 + b) / 3.0;
+    let q = (2.0 / 27.0 * a * sq_a - a * b / 3.0 + c) / 2.0;
+    let cb_p = p * p * p;
+    let discriminant = q * q + cb_p;
+
+    // Relative to the size of its terms, so cubics with small coefficients
+    // aren't all mistaken for having a double root
+    if discriminant.abs() < EPSILON * (q * q).max(cb_p.abs()).max(EPSILON) {
+        if q.abs() < EPSILON {
+            roots.push(0.0);
+        } else {
+            let u = (-q).cbrt();
+            roots.push(2.0 * u);
+            roots.push(-u);
+        }
+    } else if discriminant < 0.0 {
+        let phi = (-q / (-cb_p).sqrt()).clamp(-1.0, 1.0).acos() / 3.0;
+        let t = 2.0 * (-p).sqrt();
+        roots.push(t * phi.cos());
+        roots.push(-t * (phi + f64::consts::FRAC_PI_3).cos());
+        roots.push(-t * (phi - f64::consts::FRAC_PI_3).cos());
+    } else {
+        let sqrt_d = discriminant.sqrt();
+        roots.push((sqrt_d - q).cbrt() - (sqrt_d + q).cbrt());
+    }
+
+    for root in roots.values[..roots.count].iter_mut() {
+        *root -= a / 3.0;
+    }
+    roots.sorted()
+}
+
+/// Real roots of `a*x^4 + b*x^3 + c*x^2 + d*x + e` using Ferrari's method,
+/// each refined with a few Newton steps on the original polynomial since the
+/// closed form loses precision when the roots differ greatly in magnitude.
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Roots {
+    if a.abs() < EPSILON {
+        return solve_cubic(b, c, d, e);
+    }
+
+    // Normal form x^4 + A x^3 + B x^2 + C x + D, then substitute x = y - A/4
+    // to get the depressed quartic y^4 + p y^2 + q y + r
+    let (a, b, c, d) = (b / a, c / a, d / a, e / a);
+    let sq_a = a * a;
+    let p = -3.0 / 8.0 * sq_a + b;
+    let q = sq_a * a / 8.0 - a * b / 2.0 + c;
+    let r = -3.0 / 256.0 * sq_a * sq_a + sq_a * b / 16.0 - a * c / 4.0 + d;
+
+    let mut roots = Roots::new();
+    if r.abs() < EPSILON {
+        // No constant term: y (y^3 + p y + q) = 0
+        roots.push(0.0);
+        for &root in solve_cubic(1.0, 0.0, p, q).iter() {
+            if root.abs() >= EPSILON {
+                roots.push(root);
+            }
+        }
+    } else {
+        // Split into two quadratics using one root of the resolvent cubic.
+        // The largest root keeps 2z - p non-negative, and since that can
+        // cancel to almost nothing the root is polished first
+        let (rb, rc, rd) = (-p / 2.0, -r, r * p / 2.0 - q * q / 8.0);
+        let mut z = solve_cubic(1.0, rb, rc, rd)
+            .iter()
+            .fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        for _ in 0..4 {
+            let slope = (3.0 * z + 2.0 * rb) * z + rc;
+            if slope.abs() < EPSILON {
+                break;
+            }
+            z -= (((z + rb) * z + rc) * z + rd) / slope;
+        }
+        let u = z * z - r;
+        let v = 2.0 * z - p;
+        let u = if u.abs() < EPSILON {
+            0.0
+        } else if u > 0.0 {
+            u.sqrt()
+        } else {
+            return roots;
+        };
+        let v = if v.abs() < EPSILON {
+            0.0
+        } else if v > 0.0 {
+            v.sqrt()
+        } else {
+            return roots;
+        };
+
+        let v = if q < 0.0 { -v } else { v };
+        for (linear, constant) in [(v, z - u), (-v, z + u)] {
+            if let Some((r0, r1)) = solve_quadratic(1.0, linear, constant) {
+                roots.push(r0);
+                roots.push(r1);
+            }
+        }
+    }
+
+    let polynomial = |x: f64| (((x + a) * x + b) * x + c) * x + d;
+    let derivative = |x: f64| ((4.0 * x + 3.0 * a) * x + 2.0 * b) * x + c;
+    for root in roots.values[..roots.count].iter_mut() {
+        *root -= a / 4.0;
+        for _ in 0..3 {
+            let slope = derivative(*root);
+            if slope.abs() < EPSILON {
+                break;
+            }
+            *root -= polynomial(*root) / slope;
+        }
+    }
+    roots.sorted()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roots(actual: &[f64], expected: &[f64]) {
+        assert_eq!(
+            actual.len(),
+            expected.len(),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    /// Coefficients of the monic polynomial with the given roots, highest
+    /// power first.
+    fn expand(roots: &[f64]) -> Vec<f64> {
+        let mut coefficients = vec![1.0];
+        for &root in roots {
+            let mut next = vec![0.0; coefficients.len() + 1];
+            for (i, &c) in coefficients.iter().enumerate() {
+                next[i] += c;
+                next[i + 1] -= c * root;
+            }
+            coefficients = next;
+        }
+        coefficients
+    }
+
+    #[test]
+    fn quadratic() {
+        let (r0, r1) = solve_quadratic(1.0, -3.0, 2.0).unwrap();
+        assert_roots(&[r0, r1], &[1.0, 2.0]);
+        assert!(solve_quadratic(1.0, 0.0, 1.0).is_none());
+
+        // Catastrophic cancellation in the textbook formula
+        let (r0, r1) = solve_quadratic(1.0, -1e8, 1.0).unwrap();
+        assert!((r0 - 1e-8).abs() < 1e-20);
+        assert!((r1 - 1e8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cubic() {
+        let c = expand(&[-2.0, 1.0, 3.0]);
+        assert_roots(&solve_cubic(c[0], c[1], c[2], c[3]), &[-2.0, 1.0, 3.0]);
+
+        // One real root, two complex
+        assert_roots(&solve_cubic(1.0, 0.0, 1.0, -2.0), &[1.0]);
+
+        // Double root
+        let c = expand(&[2.0, 2.0, -1.0]);
+        assert_roots(&solve_cubic(c[0], c[1], c[2], c[3]), &[-1.0, 2.0]);
+    }
+
+    #[test]
+    fn quartic_distinct_roots() {
+        let c = expand(&[1.0, 2.0, 3.0, 4.0]);
+        assert_roots(
+            &solve_quartic(c[0], c[1], c[2], c[3], c[4]),
+            &[1.0, 2.0, 3.0, 4.0],
+        );
+
+        let c = expand(&[-7.5, -0.25, 0.5, 12.0]);
+        let scaled: Vec<f64> = c.iter().map(|c| c * 3.0).collect();
+        assert_roots(
+            &solve_quartic(scaled[0], scaled[1], scaled[2], scaled[3], scaled[4]),
+            &[-7.5, -0.25, 0.5, 12.0],
+        );
+    }
+
+    #[test]
+    fn quartic_two_real_roots() {
+        // (x^2 + 1)(x - 3)(x + 0.5)
+        let quadratic = [1.0, 0.0, 1.0];
+        let linear = expand(&[3.0, -0.5]);
+        let mut c = [0.0; 5];
+        for (i, a) in quadratic.iter().enumerate() {
+            for (j, b) in linear.iter().enumerate() {
+                c[i + j] += a * b;
+            }
+        }
+        assert_roots(&solve_quartic(c[0], c[1], c[2], c[3], c[4]), &[-0.5, 3.0]);
+    }
+
+    #[test]
+    fn quartic_no_real_roots() {
+        assert!(solve_quartic(1.0, 0.0, 0.0, 0.0, 1.0).is_empty());
+        assert!(solve_quartic(2.0, 0.0, 3.0, 0.0, 1.0).is_empty());
+    }
+
+    #[test]
+    fn quartic_repeated_and_zero_roots() {
+        let c = expand(&[0.0, 1.0, 2.0, 5.0]);
+        assert_roots(
+            &solve_quartic(c[0], c[1], c[2], c[3], c[4]),
+            &[0.0, 1.0, 2.0, 5.0],
+        );
+
+        // Biquadratic with a double root pair: (x^2 - 4)^2
+        let roots = solve_quartic(1.0, 0.0, -8.0, 0.0, 16.0);
+        assert!(roots.iter().all(|r| (r.abs() - 2.0).abs() < 1e-4));
+        assert!(roots.iter().any(|&r| r < 0.0) && roots.iter().any(|&r| r > 0.0));
+    }
+
+    #[test]
+    fn quartic_falls_back_to_cubic() {
+        let c = expand(&[-1.0, 0.5, 2.0]);
+        assert_roots(
+            &solve_quartic(0.0, c[0], c[1], c[2], c[3]),
+            &[-1.0, 0.5, 2.0],
+        );
+    }
+
+    #[test]
+    fn quartic_ill_conditioned_resolvent() {
+        // A ray through the tube of a torus, where 2z - p nearly cancels and
+        // an imprecise resolvent root lost the intersections
+        let c = [
+            1.0,
+            -1.5358722773398468,
+            0.6194537681124588,
+            -0.02344873432172656,
+            -0.017195192635161116,
+        ];
+        let roots = solve_quartic(c[0], c[1], c[2], c[3], c[4]);
+        assert_eq!(roots.len(), 2, "{:?}", &*roots);
+        for &root in roots.iter() {
+            let value = (((c[0] * root + c[1]) * root + c[2]) * root + c[3]) * root + c[4];
+            assert!(value.abs() < 1e-9, "{:?}", &*roots);
+        }
+    }
+
+    #[test]
+    fn non_finite_coefficients() {
+        assert!(solve_cubic(1.0, f64::NAN, 0.0, -1.0).is_empty());
+        assert!(solve_quartic(1.0, 0.0, f64::NAN, 0.0, -1.0).is_empty());
+        assert!(solve_quartic(1.0, f64::INFINITY, 0.0, 0.0, -1.0)
+            .iter()
+            .all(|root| root.is_finite()));
+    }
+
+    #[test]
+    fn quartic_torus_like_magnitudes() {
+        // Roots spread over a wide range, as when a ray starts far from a torus
+        let expected = [95.0, 95.5, 104.5, 105.0];
+        let c = expand(&expected);
+        assert_roots(&solve_quartic(c[0], c[1], c[2], c[3], c[4]), &expected);
+    }
+}
@@ -0,0 +1,8 @@
+mod operations;
+#[allow(clippy::module_inception)]
+mod sdf;
+mod shapes;
+
+pub use operations::*;
+pub use sdf::*;
+pub use shapes::*;
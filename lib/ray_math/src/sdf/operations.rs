@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use crate::{Point3, Vec3};
+
+use super::Sdf;
+
+/// Polynomial smooth minimum: blends `a` and `b` where they are within `k` of
+/// each other.
+fn smooth_min(a: f64, b: f64, k: f64) -> f64 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+    let h = (0.5 + 0.5 * (b - a) / k).clamp(0.0, 1.0);
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+pub struct Translate {
+    offset: Vec3,
+    inner: Arc<dyn Sdf>,
+}
+
+impl Translate {
+    pub fn new(offset: Vec3, inner: Arc<dyn Sdf>) -> Self {
+        Self { offset, inner }
+    }
+}
+
+impl Sdf for Translate {
+    fn distance(&self, point: &Point3) -> f64 {
+        self.inner.distance(&(*point - self.offset))
+    }
+
+    fn lipschitz_bound(&self) -> f64 {
+        self.inner.lipschitz_bound()
+    }
+}
+
+/// Union of two shapes, with the seam between them filleted over a distance
+/// of `smoothness`.
+pub struct SmoothUnion {
+    a: Arc<dyn Sdf>,
+    b: Arc<dyn Sdf>,
+    smoothness: f64,
+}
+
+impl SmoothUnion {
+    pub fn new(a: Arc<dyn Sdf>, b: Arc<dyn Sdf>, smoothness: f64) -> Self {
+        Self { a, b, smoothness }
+    }
+}
+
+impl Sdf for SmoothUnion {
+    fn distance(&self, point: &Point3) -> f64 {
+        smooth_min(
+            self.a.distance(point),
+            self.b.distance(point),
+            self.smoothness,
+        )
+    }
+
+    fn lipschitz_bound(&self) -> f64 {
+        self.a.lipschitz_bound().max(self.b.lipschitz_bound())
+    }
+}
+
+/// Intersection of two shapes, with the edge between them rounded over a
+/// distance of `smoothness`.
+pub struct SmoothIntersection {
+    a: Arc<dyn Sdf>,
+    b: Arc<dyn Sdf>,
+    smoothness: f64,
+}
+
+impl SmoothIntersection {
+    pub fn new(a: Arc<dyn Sdf>, b: Arc<dyn Sdf>, smoothness: f64) -> Self {
+        Self { a, b, smoothness }
+    }
+}
+
+impl Sdf for SmoothIntersection {
+    fn distance(&self, point: &Point3) -> f64 {
+        -smooth_min(
+            -self.a.distance(point),
+            -self.b.distance(point),
+            self.smoothness,
+        )
+    }
+
+    fn lipschitz_bound(&self) -> f64 {
+        self.a.lipschitz_bound().max(self.b.lipschitz_bound())
+    }
+}
+
+/// Shape `a` with shape `b` carved out of it, with the edge rounded over a
+/// distance of `smoothness`.
+pub struct SmoothDifference {
+    a: Arc<dyn Sdf>,
+    b: Arc<dyn Sdf>,
+    smoothness: f64,
+}
+
+impl SmoothDifference {
+    pub fn new(a: Arc<dyn Sdf>, b: Arc<dyn Sdf>, smoothness: f64) -> Self {
+        Self { a, b, smoothness }
+    }
+}
+
+impl Sdf for SmoothDifference {
+    fn distance(&self, point: &Point3) -> f64 {
+        -smooth_min(
+            -self.a.distance(point),
+            self.b.distance(point),
+            self.smoothness,
+        )
+    }
+
+    fn lipschitz_bound(&self) -> f64 {
+        self.a.lipschitz_bound().max(self.b.lipschitz_bound())
+    }
+}
+
+/// Infinitely many copies of a shape, one every `period` along each axis. An
+/// axis with a period of zero is not repeated along.
+///
+/// The shape should fit within one period, otherwise copies get cut off.
+pub struct Repeat {
+    period: Vec3,
+    inner: Arc<dyn Sdf>,
+}
+
+impl Repeat {
+    pub fn new(period: Vec3, inner: Arc<dyn Sdf>) -> Self {
+        Self { period, inner }
+    }
+}
+
+impl Sdf for Repeat {
+    fn distance(&self, point: &Point3) -> f64 {
+        let mut local = *point;
+        for axis in 0..3 {
+            let period = self.period[axis];
+            if period > 0.0 {
+                local[axis] -= period * (local[axis] / period).round();
+            }
+        }
+        self.inner.distance(&local)
+    }
+
+    fn lipschitz_bound(&self) -> f64 {
+        self.inner.lipschitz_bound()
+    }
+}
+
+/// A shape twisted around the Y axis by `rate` radians per unit of height.
+/// Twisting stretches space more the further it is from the axis, so
+/// `max_radius` (how far the shape reaches from the axis) is needed to bound
+/// how much the distances are distorted.
+pub struct Twist {
+    rate: f64,
+    max_radius: f64,
+    inner: Arc<dyn Sdf>,
+}
+
+impl Twist {
+    pub fn new(rate: f64, max_radius: f64, inner: Arc<dyn Sdf>) -> Self {
+        Self {
+            rate,
+            max_radius,
+            inner,
+        }
+    }
+}
+
+impl Sdf for Twist {
+    fn distance(&self, point: &Point3) -> f64 {
+        let (sin, cos) = (self.rate * point.y()).sin_cos();
+        let twisted = Point3::new(
+            cos * point.x() - sin * point.z(),
+            point.y(),
+            sin * point.x() + cos * point.z(),
+        );
+        self.inner.distance(&twisted)
+    }
+
+    fn lipschitz_bound(&self) -> f64 {
+        let stretch = self.rate * self.max_radius;
+        self.inner.lipschitz_bound() * (1.0 + stretch * stretch).sqrt()
+    }
+}
@@ -0,0 +1,15 @@
+use crate::Point3;
+
+/// A signed distance field: the distance from a point to the nearest surface
+/// of a shape, negative inside it.
+pub trait Sdf: Send + Sync {
+    fn distance(&self, point: &Point3) -> f64;
+
+    /// How much faster than the true distance the field can change. Exact
+    /// distance fields have a bound of one; operations that stretch space
+    /// (such as twisting) raise it, and sphere tracing takes smaller steps to
+    /// compensate.
+    fn lipschitz_bound(&self) -> f64 {
+        1.0
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Point3, Vec3};
+
+use super::Sdf;
+
+pub struct SdfSphere {
+    radius: f64,
+}
+
+impl SdfSphere {
+    pub fn new(radius: f64) -> Self {
+        Self { radius }
+    }
+}
+
+impl Sdf for SdfSphere {
+    fn distance(&self, point: &Point3) -> f64 {
+        point.length() - self.radius
+    }
+}
+
+/// A box centered on the origin with corners rounded off by `rounding`.
+pub struct SdfBox {
+    half_size: Vec3,
+    rounding: f64,
+}
+
+impl SdfBox {
+    pub fn new(size: Vec3, rounding: f64) -> Self {
+        Self {
+            half_size: 0.5 * size - Vec3::one() * rounding,
+            rounding,
+        }
+    }
+}
+
+impl Sdf for SdfBox {
+    fn distance(&self, point: &Point3) -> f64 {
+        let q = Vec3::new(
+            point.x().abs() - self.half_size.x(),
+            point.y().abs() - self.half_size.y(),
+            point.z().abs() - self.half_size.z(),
+        );
+        let outside = Vec3::new(q.x().max(0.0), q.y().max(0.0), q.z().max(0.0)).length();
+        let inside = q.x().max(q.y()).max(q.z()).min(0.0);
+        outside + inside - self.rounding
+    }
+}
+
+/// A ring around the Y axis, like `Torus`.
+pub struct SdfTorus {
+    major_radius: f64,
+    minor_radius: f64,
+}
+
+impl SdfTorus {
+    pub fn new(major_radius: f64, minor_radius: f64) -> Self {
+        Self {
+            major_radius,
+            minor_radius,
+        }
+    }
+}
+
+impl Sdf for SdfTorus {
+    fn distance(&self, point: &Point3) -> f64 {
+        let ring = (point.x() * point.x() + point.z() * point.z()).sqrt() - self.major_radius;
+        (ring * ring + point.y() * point.y()).sqrt() - self.minor_radius
+    }
+}
+
+/// A line segment from `a` to `b` thickened by `radius`.
+pub struct SdfCapsule {
+    a: Point3,
+    b: Point3,
+    radius: f64,
+}
+
+impl SdfCapsule {
+    pub fn new(a: Point3, b: Point3, radius: f64) -> Self {
+        Self { a, b, radius }
+    }
+}
+
+impl Sdf for SdfCapsule {
+    fn distance(&self, point: &Point3) -> f64 {
+        let pa = *point - self.a;
+        let ba = self.b - self.a;
+        let h = (pa.dot(&ba) / ba.length_squared()).clamp(0.0, 1.0);
+        (pa - h * ba).length() - self.radius
+    }
+}
@@ -0,0 +1,185 @@
+use std::{f64, ops::Range, sync::Arc};
+
+use crate::{
+    disk::{azimuth_tangent, azimuth_u},
+    material::Material,
+    sdf::Sdf,
+    Aabb, HitResult, Hittable, Point3, Ray, Transform, Vec3,
+};
+
+/// Distance from the surface at which sphere tracing counts as a hit.
+const SURFACE_DISTANCE: f64 = 1e-5;
+const MAX_STEPS: usize = 512;
+
+/// A shape described by a signed distance field, positioned by a transform.
+/// Rays are sphere traced (stepping forward by the distance to the nearest
+/// surface) within `bounds`, which are relative to the transform's position and
+/// must contain the whole visible shape.
+pub struct SdfObject<T> {
+    transform: T,
+    sdf: Arc<dyn Sdf>,
+    bounds: Aabb,
+    material: Arc<dyn Material>,
+}
+
+impl<T: Transform> SdfObject<T> {
+    pub fn from(
+        transform: T,
+        sdf: Arc<dyn Sdf>,
+        bounds: Aabb,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            transform,
+            sdf,
+            bounds,
+            material,
+        }
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn sdf(&self) -> &dyn Sdf {
+        &*self.sdf
+    }
+
+    /// Surface normal from the gradient of the distance field, estimated with
+    /// central differences.
+    fn normal(&self, local: &Point3) -> Vec3 {
+        const H: f64 = 1e-5;
+        let mut gradient = Vec3::zero();
+        for axis in 0..3 {
+            let mut offset = Vec3::zero();
+            offset[axis] = H;
+            gradient[axis] =
+                self.sdf.distance(&(*local + offset)) - self.sdf.distance(&(*local - offset));
+        }
+        gradient.normalized()
+    }
+}
+
+impl<T: Transform> Hittable for SdfObject<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let position = self.transform.position(ray.time());
+        let local_ray = Ray::new(ray.origin() - position, ray.direction(), ray.time());
+        let (t_enter, t_exit) = self.bounds.intersect(&local_ray, t_min, t_max)?;
+
+        // Rays that start inside the shape (e.g. refracted into it) march on
+        // the magnitude of the distance until they reach the surface from
+        // within. A change of sign means a step crossed the surface. Which
+        // side the ray is on is only decided once it is clear of the surface,
+        // so rays leaving a surface (at `t_min`) don't hit it again.
+        let step_scale = 1.0 / (self.sdf.lipschitz_bound() * ray.direction().length());
+        let mut t = t_enter;
+        let mut inside = None;
+        for _ in 0..MAX_STEPS {
+            let distance = self.sdf.distance(&local_ray.at(t));
+            let crossed = match inside {
+                None => {
+                    if distance.abs() >= SURFACE_DISTANCE {
+                        inside = Some(distance < 0.0);
+                    }
+                    false
+                }
+                Some(inside) => distance.abs() < SURFACE_DISTANCE || (distance < 0.0) != inside,
+            };
+            if crossed {
+                let local = local_ray.at(t);
+                let outward_normal = self.normal(&local);
+                // UV follows the normal, so scale it as if the surface were a
+                // sphere around the local origin through the hit
+                let radius = local.length();
+                let around = azimuth_tangent(&outward_normal).length();
+                return Some(
+                    HitResult::new(
+                        ray,
+                        ray.at(t),
+                        outward_normal,
+                        t,
+                        (azimuth_u(&outward_normal), 0.5 * (outward_normal.y() + 1.0)),
+                        Arc::clone(&self.material),
+                    )
+                    .with_tangent(azimuth_tangent(&outward_normal))
+                    .with_uv_scale(
+                        2.0 * f64::consts::PI * radius * around,
+                        2.0 * radius / around.max(1e-6),
+                    ),
+                );
+            }
+
+            // Never step by less than the hit distance, so marching away from
+            // a surface doesn't stall
+            t += distance.abs().max(SURFACE_DISTANCE) * step_scale;
+            if t > t_exit {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        Some(Aabb::swept(&self.bounds, &self.transform, time_range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{
+        material::Lambertian, sdf::SdfSphere, texture::SolidColor, Color, Sphere, StaticTransform,
+    };
+
+    #[test]
+    fn sphere_traced_hits_match_a_sphere() {
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        let center = Point3::new(1.0, -2.0, 0.5);
+        let traced = SdfObject::from(
+            StaticTransform::new(center),
+            Arc::new(SdfSphere::new(1.0)),
+            Aabb::new(-1.1 * Vec3::one(), 1.1 * Vec3::one()),
+            material.clone(),
+        );
+        let sphere = Sphere::from(StaticTransform::new(center), 1.0, material);
+
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut hits = 0;
+        for n in 0..1000 {
+            // Some rays start inside, as refracted rays do
+            let origin = if n % 4 == 0 {
+                center + 0.9 * Vec3::random_in_unit_sphere(&mut rng)
+            } else {
+                center + Point3::random(&mut rng, -3.0, 3.0)
+            };
+            let direction = Vec3::random_unit(&mut rng) * 0.5;
+            let ray = Ray::new(origin, direction, 0.0);
+            let expected = sphere.hit(&ray, 0.001, f64::INFINITY);
+            let actual = traced.hit(&ray, 0.001, f64::INFINITY);
+            match (expected, actual) {
+                (Some(expected), Some(actual)) => {
+                    // Sphere tracing stops within a small distance of the
+                    // surface, which at grazing angles can be a little way
+                    // before the true hit along the ray
+                    let distance = (actual.point() - center).length() - 1.0;
+                    assert!(distance.abs() < 1e-4, "{}", distance);
+                    assert!((expected.point() - actual.point()).length() < 1e-2);
+                    assert!(actual.normal().dot(&expected.normal()) > 0.999);
+                    hits += 1;
+                }
+                (None, None) => {}
+                (expected, actual) => {
+                    panic!(
+                        "{:?} != {:?}",
+                        expected.map(|hit| hit.t()),
+                        actual.map(|hit| hit.t())
+                    )
+                }
+            }
+        }
+        assert!(hits > 200);
+    }
+}
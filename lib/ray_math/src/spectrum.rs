@@ -0,0 +1,239 @@
+use std::sync::OnceLock;
+
+use rand::Rng;
+
+use crate::{Color, Vec3};
+
+/// Shortest wavelength traced, in nanometers.
+pub const MIN_WAVELENGTH: f64 = 380.0;
+/// Longest wavelength traced, in nanometers.
+pub const MAX_WAVELENGTH: f64 = 720.0;
+
+/// Steps used to integrate over the visible range.
+const INTEGRATION_STEPS: usize = 1000;
+
+/// Smits' basis spectra for turning RGB into a smooth spectrum, from "An RGB
+/// to Spectrum Conversion for Reflectances", sampled at the centers of ten
+/// equal bins over the visible range.
+const SMITS_WHITE: [f64; 10] = [
+    1.0000, 1.0000, 0.9999, 0.9993, 0.9992, 0.9998, 1.0000, 1.0000, 1.0000, 1.0000,
+];
+const SMITS_CYAN: [f64; 10] = [
+    0.9710, 0.9426, 1.0007, 1.0007, 1.0007, 1.0007, 0.1564, 0.0000, 0.0000, 0.0000,
+];
+const SMITS_MAGENTA: [f64; 10] = [
+    1.0000, 1.0000, 0.9685, 0.2229, 0.0000, 0.0458, 0.8369, 1.0000, 1.0000, 0.9959,
+];
+const SMITS_YELLOW: [f64; 10] = [
+    0.0001, 0.0000, 0.1088, 0.6651, 1.0000, 1.0000, 0.9996, 0.9586, 0.9685, 0.9840,
+];
+const SMITS_RED: [f64; 10] = [
+    0.1012, 0.0515, 0.0000, 0.0000, 0.0000, 0.0000, 0.8325, 1.0149, 1.0149, 1.0149,
+];
+const SMITS_GREEN: [f64; 10] = [
+    0.0000, 0.0000, 0.0273, 0.7937, 1.0000, 0.9418, 0.1719, 0.0000, 0.0000, 0.0025,
+];
+const SMITS_BLUE: [f64; 10] = [
+    1.0000, 1.0000, 0.8916, 0.3323, 0.0000, 0.0000, 0.0003, 0.0369, 0.0483, 0.0496,
+];
+
+/// The CIE 1931 standard observer's color matching functions at `wavelength`
+/// nanometers, using the multi-lobe fit from Wyman et al.'s "Simple Analytic
+/// Approximations to the CIE XYZ Color Matching Functions".
+pub fn wavelength_to_xyz(wavelength: f64) -> Vec3 {
+    let lobe = |mean: f64, below: f64, above: f64| {
+        let width = if wavelength < mean { below } else { above };
+        let x = (wavelength - mean) / width;
+        (-0.5 * x * x).exp()
+    };
+    Vec3::new(
+        1.056 * lobe(599.8, 37.9, 31.0) + 0.362 * lobe(442.0, 16.0, 26.7)
+            - 0.065 * lobe(501.1, 20.4, 26.2),
+        0.821 * lobe(568.8, 46.9, 40.5) + 0.286 * lobe(530.9, 16.3, 31.1),
+        1.217 * lobe(437.0, 11.8, 36.0) + 0.681 * lobe(459.0, 26.0, 13.8),
+    )
+}
+
+/// Converts CIE XYZ to linear sRGB with a D65 white point.
+pub fn xyz_to_rgb(xyz: &Vec3) -> Color {
+    let (x, y, z) = (xyz.x(), xyz.y(), xyz.z());
+    Color::new(
+        3.2406 * x - 1.5372 * y - 0.4986 * z,
+        -0.9689 * x + 1.8758 * y + 0.0415 * z,
+        0.0557 * x - 0.2040 * y + 1.0570 * z,
+    )
+}
+
+/// Picks a wavelength uniformly over the visible range, returning it with the
+/// weight that turns light of only that wavelength back into RGB. The weights
+/// average to white over all wavelengths, so a path that takes on a
+/// wavelength partway through still converges to the right color.
+pub fn sample_wavelength(rng: &mut dyn rand::RngCore) -> (f64, Color) {
+    let wavelength = rng.gen_range(MIN_WAVELENGTH..MAX_WAVELENGTH);
+    (wavelength, wavelength_to_rgb(wavelength) * *white_balance())
+}
+
+/// The sRGB color of a single wavelength, without the out of gamut negative
+/// parts.
+fn wavelength_to_rgb(wavelength: f64) -> Color {
+    let rgb = xyz_to_rgb(&wavelength_to_xyz(wavelength));
+    Color::new(rgb.x().max(0.0), rgb.y().max(0.0), rgb.z().max(0.0))
+}
+
+/// The inverse of the average of `wavelength_to_rgb` over the visible range,
+/// per channel.
+fn white_balance() -> &'static Color {
+    static WHITE_BALANCE: OnceLock<Color> = OnceLock::new();
+    WHITE_BALANCE.get_or_init(|| {
+        let step = (MAX_WAVELENGTH - MIN_WAVELENGTH) / INTEGRATION_STEPS as f64;
+        let mean = (0..INTEGRATION_STEPS)
+            .map(|i| wavelength_to_rgb(MIN_WAVELENGTH + (i as f64 + 0.5) * step))
+            .fold(Color::zero(), |a, b| a + b)
+            / INTEGRATION_STEPS as f64;
+        Color::new(1.0 / mean.x(), 1.0 / mean.y(), 1.0 / mean.z())
+    })
+}
+
+/// The value at `wavelength` of a smooth spectrum that looks like `rgb`, by
+/// Smits' method: the smallest channel is made of white, the next of the
+/// secondary color over it, and the rest of the primary. Used to trace RGB
+/// albedos and lights at a single wavelength.
+pub fn rgb_to_spectrum(rgb: &Color, wavelength: f64) -> f64 {
+    let basis = |spectrum: &[f64; 10]| {
+        let position =
+            (wavelength - MIN_WAVELENGTH) / (MAX_WAVELENGTH - MIN_WAVELENGTH) * 10.0 - 0.5;
+        let position = position.clamp(0.0, 9.0);
+        let index = (position as usize).min(8);
+        let fraction = position - index as f64;
+        spectrum[index] * (1.0 - fraction) + spectrum[index + 1] * fraction
+    };
+
+    let (r, g, b) = (rgb.x(), rgb.y(), rgb.z());
+    if r <= g && r <= b {
+        r * basis(&SMITS_WHITE)
+            + if g <= b {
+                (g - r) * basis(&SMITS_CYAN) + (b - g) * basis(&SMITS_BLUE)
+            } else {
+                (b - r) * basis(&SMITS_CYAN) + (g - b) * basis(&SMITS_GREEN)
+            }
+    } else if g <= r && g <= b {
+        g * basis(&SMITS_WHITE)
+            + if r <= b {
+                (r - g) * basis(&SMITS_MAGENTA) + (b - r) * basis(&SMITS_BLUE)
+            } else {
+                (b - g) * basis(&SMITS_MAGENTA) + (r - b) * basis(&SMITS_RED)
+            }
+    } else {
+        b * basis(&SMITS_WHITE)
+            + if r <= g {
+                (r - b) * basis(&SMITS_YELLOW) + (g - r) * basis(&SMITS_GREEN)
+            } else {
+                (g - b) * basis(&SMITS_YELLOW) + (r - g) * basis(&SMITS_RED)
+            }
+    }
+}
+
+/// Film for spectral rendering, accumulating samples of single wavelengths
+/// into CIE XYZ. Wavelengths must be picked uniformly over the visible range.
+#[derive(Debug, Clone, Copy)]
+pub struct XyzFilm {
+    sum: Vec3,
+    samples: usize,
+}
+
+impl XyzFilm {
+    pub fn new() -> Self {
+        Self {
+            sum: Vec3::zero(),
+            samples: 0,
+        }
+    }
+
+    /// Picks the wavelength for sample `index` of `count`, within its own
+    /// slice of the visible range so a pixel's samples cover the spectrum
+    /// evenly, which keeps down color noise.
+    pub fn sample_wavelength(rng: &mut dyn rand::RngCore, index: usize, count: usize) -> f64 {
+        let u = (index as f64 + rng.gen_range(0.0..1.0)) / count as f64;
+        MIN_WAVELENGTH + u * (MAX_WAVELENGTH - MIN_WAVELENGTH)
+    }
+
+    /// Adds the radiance a path carried at `wavelength`.
+    pub fn add_sample(&mut self, wavelength: f64, radiance: f64) {
+        // Divided by the uniform probability of picking the wavelength
+        self.sum += (radiance * (MAX_WAVELENGTH - MIN_WAVELENGTH)) * wavelength_to_xyz(wavelength);
+        self.samples += 1;
+    }
+
+    /// The mean of the samples so far, with a flat spectrum of 1 having
+    /// `Y = 1`.
+    pub fn xyz(&self) -> Vec3 {
+        if self.samples == 0 {
+            return Vec3::zero();
+        }
+        self.sum / (self.samples as f64 * flat_spectrum_xyz().y())
+    }
+
+    /// The film's color in linear sRGB, white balanced so that a flat
+    /// spectrum of 1, which is what white RGB albedos and lights turn into,
+    /// comes out as white.
+    pub fn rgb(&self) -> Color {
+        let white = xyz_to_rgb(&(*flat_spectrum_xyz() / flat_spectrum_xyz().y()));
+        let rgb = xyz_to_rgb(&self.xyz());
+        Color::new(
+            rgb.x() / white.x(),
+            rgb.y() / white.y(),
+            rgb.z() / white.z(),
+        )
+    }
+}
+
+impl Default for XyzFilm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The XYZ color of a flat spectrum of 1 over the visible range.
+fn flat_spectrum_xyz() -> &'static Vec3 {
+    static FLAT_SPECTRUM_XYZ: OnceLock<Vec3> = OnceLock::new();
+    FLAT_SPECTRUM_XYZ.get_or_init(|| {
+        let step = (MAX_WAVELENGTH - MIN_WAVELENGTH) / INTEGRATION_STEPS as f64;
+        (0..INTEGRATION_STEPS)
+            .map(|i| wavelength_to_xyz(MIN_WAVELENGTH + (i as f64 + 0.5) * step))
+            .fold(Vec3::zero(), |a, b| a + b)
+            * step
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wavelengths(count: usize) -> impl Iterator<Item = f64> {
+        (0..count).map(move |i| {
+            MIN_WAVELENGTH + (i as f64 + 0.5) / count as f64 * (MAX_WAVELENGTH - MIN_WAVELENGTH)
+        })
+    }
+
+    #[test]
+    fn white_is_a_flat_spectrum() {
+        for wavelength in wavelengths(100) {
+            let value = rgb_to_spectrum(&Color::one(), wavelength);
+            assert!((value - 1.0).abs() < 1e-3, "{} at {}", value, wavelength);
+            let value = rgb_to_spectrum(&(0.3 * Color::one()), wavelength);
+            assert!((value - 0.3).abs() < 1e-3, "{} at {}", value, wavelength);
+        }
+    }
+
+    #[test]
+    fn flat_spectrum_film_is_white() {
+        let mut film = XyzFilm::new();
+        for wavelength in wavelengths(1000) {
+            film.add_sample(wavelength, 1.0);
+        }
+        let rgb = film.rgb();
+        for channel in 0..3 {
+            assert!((rgb[channel] - 1.0).abs() < 1e-3, "{:?}", rgb);
+        }
+    }
+}
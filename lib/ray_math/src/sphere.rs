@@ -1,6 +1,10 @@
 use std::{f64, ops::Range, sync::Arc};
 
-use crate::{material::Material, Aabb, HitResult, Hittable, Point3, Ray, Transform, Vec3};
+use crate::{
+    csg::convex_interval, disk::azimuth_tangent, material::Material, Aabb, HitResult, Hittable,
+    Interval, LaneMask, Lanes, PacketHitResult, Point3, Ray, RayPacket, Solid, Transform, Vec3,
+    PACKET_WIDTH,
+};
 
 pub struct Sphere<T> {
     transform: T,
@@ -25,18 +29,21 @@ impl<T: Transform> Sphere<T> {
         self.radius
     }
 
-    /// `point`: a given point on the sphere of radius one, centered at the origin.
-    /// `u`: returned value [0,1] of angle around the Y axis from X=-1.
-    /// `v`: returned value [0,1] of angle from Y=-1 to Y=+1.
-    ///     <1 0 0> yields <0.50 0.50>       <-1  0  0> yields <0.00 0.50>
-    ///     <0 1 0> yields <0.50 1.00>       < 0 -1  0> yields <0.50 0.00>
-    ///     <0 0 1> yields <0.25 0.50>       < 0  0 -1> yields <0.75 0.50>
-    fn get_uv(point: &Point3) -> (f64, f64) {
-        let theta = (-point.y()).acos();
-        let phi = (-point.z()).atan2(point.x()) + f64::consts::PI;
-        (
-            phi * 0.5 * f64::consts::FRAC_1_PI,
-            theta * f64::consts::FRAC_1_PI,
+    fn make_hit(&self, ray: &Ray, center: Point3, t: f64) -> HitResult {
+        let point = ray.at(t);
+        let outward_normal = (point - center) / self.radius;
+        HitResult::new(
+            ray,
+            point,
+            outward_normal,
+            t,
+            sphere_uv(&outward_normal),
+            Arc::clone(&self.material),
+        )
+        .with_tangent(azimuth_tangent(&outward_normal))
+        .with_uv_scale(
+            2.0 * f64::consts::PI * self.radius * azimuth_tangent(&outward_normal).length(),
+            f64::consts::PI * self.radius,
         )
     }
 }
@@ -63,16 +70,7 @@ impl<T: Transform> Hittable for Sphere<T> {
             }
         }
 
-        let point = ray.at(root);
-        let outward_normal = (point - center) / self.radius;
-        Some(HitResult::new(
-            ray,
-            point,
-            outward_normal,
-            root,
-            Self::get_uv(&outward_normal),
-            Arc::clone(&self.material),
-        ))
+        Some(self.make_hit(ray, center, root))
     }
 
     fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
@@ -86,4 +84,79 @@ impl<T: Transform> Hittable for Sphere<T> {
 
         Some(Aabb::surround(&box0, &box1))
     }
+
+    fn hit_packet(
+        &self,
+        packet: &RayPacket,
+        t_min: f64,
+        t_max: &Lanes,
+        active: &LaneMask,
+    ) -> PacketHitResult {
+        // Lanes can be at different times, so each one gets its own center
+        let mut centers = [Point3::zero(); PACKET_WIDTH];
+        let mut center = [[0.0; PACKET_WIDTH]; 3];
+        for (lane, &time) in packet.time().iter().enumerate() {
+            centers[lane] = self.transform.position(time);
+            for (axis, component) in center.iter_mut().enumerate() {
+                component[lane] = centers[lane][axis];
+            }
+        }
+
+        let mut a = [0.0; PACKET_WIDTH];
+        let mut half_b = [0.0; PACKET_WIDTH];
+        let mut c = [-self.radius * self.radius; PACKET_WIDTH];
+        for (axis, center) in center.iter().enumerate() {
+            let origin = packet.origin(axis);
+            let direction = packet.direction(axis);
+            for lane in 0..PACKET_WIDTH {
+                let oc = origin[lane] - center[lane];
+                a[lane] += direction[lane] * direction[lane];
+                half_b[lane] += oc * direction[lane];
+                c[lane] += oc * oc;
+            }
+        }
+
+        let mut result = PacketHitResult::default();
+        for lane in 0..PACKET_WIDTH {
+            let discriminant = half_b[lane] * half_b[lane] - a[lane] * c[lane];
+            if !active[lane] || discriminant < 0.0 {
+                continue;
+            }
+
+            let sqrtd = discriminant.sqrt();
+            let near = (-half_b[lane] - sqrtd) / a[lane];
+            let far = (-half_b[lane] + sqrtd) / a[lane];
+            let root = if t_min <= near && near <= t_max[lane] {
+                near
+            } else if t_min <= far && far <= t_max[lane] {
+                far
+            } else {
+                continue;
+            };
+
+            result[lane] = Some(self.make_hit(packet.ray(lane), centers[lane], root));
+        }
+        result
+    }
+}
+
+impl<T: Transform> Solid for Sphere<T> {
+    fn intervals(&self, ray: &Ray, out: &mut Vec<Interval>) {
+        convex_interval(|t_min, t_max| self.hit(ray, t_min, t_max), out);
+    }
+}
+
+/// `point`: a given point on the sphere of radius one, centered at the origin.
+/// `u`: returned value [0,1] of angle around the Y axis from X=-1.
+/// `v`: returned value [0,1] of angle from Y=-1 to Y=+1.
+///     <1 0 0> yields <0.50 0.50>       <-1  0  0> yields <0.00 0.50>
+///     <0 1 0> yields <0.50 1.00>       < 0 -1  0> yields <0.50 0.00>
+///     <0 0 1> yields <0.25 0.50>       < 0  0 -1> yields <0.75 0.50>
+pub(crate) fn sphere_uv(point: &Point3) -> (f64, f64) {
+    let theta = (-point.y()).acos();
+    let phi = (-point.z()).atan2(point.x()) + f64::consts::PI;
+    (
+        phi * 0.5 * f64::consts::FRAC_1_PI,
+        theta * f64::consts::FRAC_1_PI,
+    )
 }
@@ -0,0 +1,539 @@
+use std::{
+    collections::{HashMap, HashSet},
+    f64,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use crate::{Mesh, Point3};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubdivisionScheme {
+    /// For triangle meshes: each triangle becomes four. Other polygons are
+    /// split into triangles first.
+    Loop,
+    /// For quad-dominant meshes: each n-sided face becomes n quads.
+    CatmullClark,
+}
+
+/// A polygon mesh that is the control cage of a subdivision surface. Edges can
+/// be given a crease sharpness: an edge with sharpness `s` is refined with the
+/// sharp (crease) rules for `s` levels and smoothly after that, and fractional
+/// sharpness blends the two. Boundary edges are always sharp.
+#[derive(Clone)]
+pub struct ControlMesh {
+    positions: Vec<Point3>,
+    /// Vertex indices of each face, counter-clockwise seen from outside.
+    faces: Vec<Vec<usize>>,
+    creases: HashMap<(usize, usize), f64>,
+}
+
+/// What each vertex is connected to, rebuilt for every level of refinement.
+struct Topology {
+    /// Faces on either side of each edge, keyed by its vertices in order.
+    edge_faces: HashMap<(usize, usize), Vec<usize>>,
+    /// Other end of every edge from each vertex.
+    neighbors: Vec<Vec<usize>>,
+    vertex_faces: Vec<Vec<usize>>,
+}
+
+impl ControlMesh {
+    /// # Panics
+    ///
+    /// Panics if a face has fewer than three vertices, uses the same vertex
+    /// more than once or refers to a vertex that doesn't exist
+    pub fn new(positions: Vec<Point3>, faces: Vec<Vec<usize>>) -> Self {
+        assert!(
+            faces.iter().all(|face| face.len() >= 3),
+            "Control mesh faces need at least three vertices"
+        );
+        assert!(
+            !faces.iter().any(|face| has_repeated_vertex(face)),
+            "Control mesh face uses a vertex more than once"
+        );
+        assert!(
+            faces.iter().flatten().all(|&i| i < positions.len()),
+            "Control mesh face vertex index out of range"
+        );
+        Self {
+            positions,
+            faces,
+            creases: HashMap::new(),
+        }
+    }
+
+    /// Sets the crease sharpness of the edge between vertices `a` and `b`.
+    /// Use `f64::INFINITY` for an edge that stays sharp at every level.
+    /// Creases between vertices that aren't joined by an edge are ignored.
+    pub fn with_crease(mut self, a: usize, b: usize, sharpness: f64) -> Self {
+        self.creases.insert(edge_key(a, b), sharpness);
+        self
+    }
+
+    /// Loads a control mesh from a Wavefront OBJ file. Only vertex positions
+    /// and faces are used (texture and normal indices are ignored), along
+    /// with OpenSubdiv style crease tags `t crease 2/1/0 <a> <b> <sharpness>`
+    /// where `a` and `b` are zero-based vertex indices.
+    pub fn load_obj<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read_obj(BufReader::new(File::open(path)?))
+    }
+
+    pub fn read_obj<R: BufRead>(reader: R) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut positions = Vec::new();
+        let mut faces = Vec::new();
+        let mut creases = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let mut fields = line.split_whitespace();
+            match fields.next() {
+                Some("v") => {
+                    let mut xyz = [0.0; 3];
+                    for value in xyz.iter_mut() {
+                        *value = fields
+                            .next()
+                            .and_then(|field| field.parse().ok())
+                            .ok_or_else(|| invalid("Invalid OBJ vertex"))?;
+                    }
+                    positions.push(Point3::new(xyz[0], xyz[1], xyz[2]));
+                }
+                Some("f") => {
+                    // Indices are one-based, or negative to count back from
+                    // the latest vertex
+                    let face = fields
+                        .map(|field| {
+                            let index: isize = field
+                                .split('/')
+                                .next()
+                                .and_then(|index| index.parse().ok())
+                                .ok_or_else(|| invalid("Invalid OBJ face"))?;
+                            let index = if index < 0 {
+                                positions.len() as isize + index
+                            } else {
+                                index - 1
+                            };
+                            if index < 0 || index as usize >= positions.len() {
+                                return Err(invalid("OBJ face vertex index out of range"));
+                            }
+                            Ok(index as usize)
+                        })
+                        .collect::<io::Result<Vec<usize>>>()?;
+                    if face.len() < 3 {
+                        return Err(invalid("OBJ face with fewer than three vertices"));
+                    }
+                    if has_repeated_vertex(&face) {
+                        return Err(invalid("OBJ face uses a vertex more than once"));
+                    }
+                    faces.push(face);
+                }
+                Some("t") if fields.next() == Some("crease") => {
+                    let mut values = fields.skip(1);
+                    let mut index = || -> Option<usize> { values.next()?.parse().ok() };
+                    let (a, b) = index()
+                        .zip(index())
+                        .ok_or_else(|| invalid("Invalid OBJ crease tag"))?;
+                    let sharpness = values
+                        .next()
+                        .and_then(|field| field.parse().ok())
+                        .ok_or_else(|| invalid("Invalid OBJ crease tag"))?;
+                    creases.push((a, b, sharpness));
+                }
+                _ => {}
+            }
+        }
+
+        if creases
+            .iter()
+            .any(|&(a, b, _)| a >= positions.len() || b >= positions.len())
+        {
+            return Err(invalid("OBJ crease vertex index out of range"));
+        }
+        let edges: HashSet<(usize, usize)> = faces
+            .iter()
+            .flat_map(|face| {
+                face.iter()
+                    .zip(face.iter().cycle().skip(1))
+                    .map(|(&a, &b)| edge_key(a, b))
+            })
+            .collect();
+        if creases
+            .iter()
+            .any(|&(a, b, _)| !edges.contains(&edge_key(a, b)))
+        {
+            return Err(invalid("OBJ crease between vertices without an edge"));
+        }
+        Ok(creases
+            .into_iter()
+            .fold(Self::new(positions, faces), |mesh, (a, b, sharpness)| {
+                mesh.with_crease(a, b, sharpness)
+            }))
+    }
+
+    pub fn positions(&self) -> &[Point3] {
+        &self.positions
+    }
+
+    pub fn faces(&self) -> &[Vec<usize>] {
+        &self.faces
+    }
+
+    /// Refines the mesh `levels` times with `scheme`.
+    pub fn subdivided(&self, scheme: SubdivisionScheme, levels: usize) -> ControlMesh {
+        let mut mesh = self.clone();
+        if scheme == SubdivisionScheme::Loop {
+            mesh.faces = mesh.triangulated_faces();
+        }
+        for _ in 0..levels {
+            mesh = match scheme {
+                SubdivisionScheme::Loop => mesh.loop_step(),
+                SubdivisionScheme::CatmullClark => mesh.catmull_clark_step(),
+            };
+        }
+        mesh
+    }
+
+    /// The number of levels needed for the longest edge of the control mesh
+    /// to cover at most `max_pixels` pixels on screen, seen from `distance`
+    /// away by a camera with the given vertical field of view and image
+    /// height. Each level halves the length of the edges.
+    pub fn levels_for_screen_size(
+        &self,
+        distance: f64,
+        vertical_field_of_view_degrees: f64,
+        image_height: usize,
+        max_pixels: f64,
+    ) -> usize {
+        let longest_edge = self
+            .faces
+            .iter()
+            .flat_map(|face| (0..face.len()).map(move |k| (face[k], face[(k + 1) % face.len()])))
+            .map(|(a, b)| (self.positions[a] - self.positions[b]).length())
+            .fold(0.0, f64::max);
+
+        let view_height = 2.0
+            * distance.max(f64::EPSILON)
+            * (vertical_field_of_view_degrees.to_radians() / 2.0).tan();
+        let pixels = longest_edge / view_height * image_height as f64;
+        if pixels <= max_pixels {
+            0
+        } else {
+            (pixels / max_pixels).log2().ceil() as usize
+        }
+    }
+
+    /// Triangulates the faces into a mesh with smooth normals.
+    pub fn to_mesh(&self) -> Mesh {
+        Mesh::new(self.positions.clone(), self.triangles()).with_smooth_normals()
+    }
+
+    fn triangles(&self) -> Vec<[usize; 3]> {
+        self.faces
+            .iter()
+            .flat_map(|face| (1..face.len() - 1).map(move |k| [face[0], face[k], face[k + 1]]))
+            .collect()
+    }
+
+    fn triangulated_faces(&self) -> Vec<Vec<usize>> {
+        self.triangles().into_iter().map(Vec::from).collect()
+    }
+
+    fn topology(&self) -> Topology {
+        let mut edge_faces: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        let mut neighbors = vec![Vec::new(); self.positions.len()];
+        let mut vertex_faces = vec![Vec::new(); self.positions.len()];
+        for (index, face) in self.faces.iter().enumerate() {
+            for k in 0..face.len() {
+                let (a, b) = (face[k], face[(k + 1) % face.len()]);
+                vertex_faces[a].push(index);
+                let faces = edge_faces.entry(edge_key(a, b)).or_default();
+                if faces.is_empty() {
+                    neighbors[a].push(b);
+                    neighbors[b].push(a);
+                }
+                faces.push(index);
+            }
+        }
+        Topology {
+            edge_faces,
+            neighbors,
+            vertex_faces,
+        }
+    }
+
+    /// Sharpness of an edge for this level, with boundary (and non-manifold)
+    /// edges always sharp.
+    fn sharpness(&self, topology: &Topology, a: usize, b: usize) -> f64 {
+        let key = edge_key(a, b);
+        if topology.edge_faces[&key].len() != 2 {
+            return f64::INFINITY;
+        }
+        self.creases.get(&key).copied().unwrap_or(0.0)
+    }
+
+    /// Creases carried over to the two halves of each split edge, one level
+    /// less sharp.
+    fn child_creases(
+        &self,
+        edge_points: &HashMap<(usize, usize), usize>,
+    ) -> HashMap<(usize, usize), f64> {
+        self.creases
+            .iter()
+            .filter(|(_, &sharpness)| sharpness > 1.0)
+            .filter_map(|(&(a, b), &sharpness)| {
+                let middle = *edge_points.get(&(a, b))?;
+                Some([
+                    (edge_key(a, middle), sharpness - 1.0),
+                    (edge_key(middle, b), sharpness - 1.0),
+                ])
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// The new position of an old vertex given its position from the smooth
+    /// rule, following the crease rules when two sharp edges meet there and
+    /// staying put (as a corner) when more do.
+    fn refined_vertex(
+        &self,
+        topology: &Topology,
+        vertex: usize,
+        smooth: Point3,
+        crease_weight: f64,
+    ) -> Point3 {
+        let position = self.positions[vertex];
+        let sharp: Vec<(usize, f64)> = topology.neighbors[vertex]
+            .iter()
+            .map(|&other| (other, self.sharpness(topology, vertex, other)))
+            .filter(|&(_, sharpness)| sharpness > 0.0)
+            .collect();
+
+        let sharp_position = match sharp.len() {
+            0 | 1 => return smooth,
+            // Corner of an open mesh, where only two boundary edges meet
+            2 if topology.neighbors[vertex].len() == 2 => position,
+            // Crease: only the two neighbours along the crease contribute
+            2 => {
+                (1.0 - 2.0 * crease_weight) * position
+                    + crease_weight * (self.positions[sharp[0].0] + self.positions[sharp[1].0])
+            }
+            _ => position,
+        };
+
+        let sharpness = sharp.iter().map(|&(_, s)| s).sum::<f64>() / sharp.len() as f64;
+        if sharpness >= 1.0 {
+            sharp_position
+        } else {
+            sharpness * sharp_position + (1.0 - sharpness) * smooth
+        }
+    }
+
+    fn loop_step(&self) -> ControlMesh {
+        let topology = self.topology();
+        let mut positions = Vec::with_capacity(self.positions.len() + topology.edge_faces.len());
+
+        for (vertex, neighbors) in topology.neighbors.iter().enumerate() {
+            let n = neighbors.len() as f64;
+            let beta = if neighbors.len() == 3 {
+                3.0 / 16.0
+            } else {
+                3.0 / (8.0 * n)
+            };
+            let sum = neighbors
+                .iter()
+                .fold(Point3::zero(), |sum, &other| sum + self.positions[other]);
+            let smooth = if neighbors.is_empty() {
+                self.positions[vertex]
+            } else {
+                (1.0 - n * beta) * self.positions[vertex] + beta * sum
+            };
+            positions.push(self.refined_vertex(&topology, vertex, smooth, 1.0 / 8.0));
+        }
+
+        let mut edge_points = HashMap::with_capacity(topology.edge_faces.len());
+        for (&(a, b), faces) in &topology.edge_faces {
+            let midpoint = 0.5 * (self.positions[a] + self.positions[b]);
+            let sharpness = self.sharpness(&topology, a, b);
+            let position = if sharpness >= 1.0 {
+                midpoint
+            } else {
+                let opposite = faces
+                    .iter()
+                    .map(|&face| {
+                        let face = &self.faces[face];
+                        let far = face.iter().find(|&&v| v != a && v != b).unwrap();
+                        self.positions[*far]
+                    })
+                    .fold(Point3::zero(), |sum, p| sum + p);
+                let smooth = 0.75 * midpoint + 0.125 * opposite;
+                sharpness * midpoint + (1.0 - sharpness) * smooth
+            };
+            edge_points.insert((a, b), positions.len());
+            positions.push(position);
+        }
+
+        let mut faces = Vec::with_capacity(self.faces.len() * 4);
+        for face in &self.faces {
+            let [a, b, c] = [face[0], face[1], face[2]];
+            let [ab, bc, ca] = [(a, b), (b, c), (c, a)].map(|(x, y)| edge_points[&edge_key(x, y)]);
+            faces.extend([
+                vec![a, ab, ca],
+                vec![ab, b, bc],
+                vec![ca, bc, c],
+                vec![ab, bc, ca],
+            ]);
+        }
+
+        ControlMesh {
+            positions,
+            faces,
+            creases: self.child_creases(&edge_points),
+        }
+    }
+
+    fn catmull_clark_step(&self) -> ControlMesh {
+        let topology = self.topology();
+        let face_points: Vec<Point3> = self
+            .faces
+            .iter()
+            .map(|face| {
+                face.iter()
+                    .fold(Point3::zero(), |sum, &v| sum + self.positions[v])
+                    / face.len() as f64
+            })
+            .collect();
+
+        let mut positions =
+            Vec::with_capacity(self.positions.len() + topology.edge_faces.len() + self.faces.len());
+
+        for (vertex, neighbors) in topology.neighbors.iter().enumerate() {
+            let position = self.positions[vertex];
+            let smooth = if neighbors.is_empty() {
+                position
+            } else {
+                // (Q + 2R + (n - 3) S) / n, with Q the average of the
+                // surrounding face points and R of the edge midpoints
+                let n = neighbors.len() as f64;
+                let faces = &topology.vertex_faces[vertex];
+                let q = faces
+                    .iter()
+                    .fold(Point3::zero(), |sum, &face| sum + face_points[face])
+                    / faces.len() as f64;
+                let r = neighbors.iter().fold(Point3::zero(), |sum, &other| {
+                    sum + 0.5 * (position + self.positions[other])
+                }) / n;
+                (q + 2.0 * r + (n - 3.0) * position) / n
+            };
+            positions.push(self.refined_vertex(&topology, vertex, smooth, 1.0 / 8.0));
+        }
+
+        let mut edge_points = HashMap::with_capacity(topology.edge_faces.len());
+        for (&(a, b), faces) in &topology.edge_faces {
+            let midpoint = 0.5 * (self.positions[a] + self.positions[b]);
+            let sharpness = self.sharpness(&topology, a, b);
+            let position = if sharpness >= 1.0 {
+                midpoint
+            } else {
+                let smooth =
+                    0.5 * midpoint + 0.25 * (face_points[faces[0]] + face_points[faces[1]]);
+                sharpness * midpoint + (1.0 - sharpness) * smooth
+            };
+            edge_points.insert((a, b), positions.len());
+            positions.push(position);
+        }
+
+        let first_face_point = positions.len();
+        positions.extend(face_points);
+
+        let mut faces = Vec::new();
+        for (index, face) in self.faces.iter().enumerate() {
+            let n = face.len();
+            for k in 0..n {
+                let (previous, vertex, next) = (face[(k + n - 1) % n], face[k], face[(k + 1) % n]);
+                faces.push(vec![
+                    vertex,
+                    edge_points[&edge_key(vertex, next)],
+                    first_face_point + index,
+                    edge_points[&edge_key(previous, vertex)],
+                ]);
+            }
+        }
+
+        ControlMesh {
+            positions,
+            faces,
+            creases: self.child_creases(&edge_points),
+        }
+    }
+}
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+fn has_repeated_vertex(face: &[usize]) -> bool {
+    face.iter()
+        .enumerate()
+        .any(|(i, vertex)| face[i + 1..].contains(vertex))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A unit square split into two triangles along the diagonal from 0 to 2,
+    /// followed by `tags`.
+    fn square_obj(tags: &str) -> String {
+        format!(
+            "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3\nf 1 3 4\n{}",
+            tags
+        )
+    }
+
+    #[test]
+    fn obj_crease_on_an_edge() {
+        let obj = square_obj("t crease 2/1/0 0 2 3\n");
+        let mesh = ControlMesh::read_obj(obj.as_bytes()).unwrap();
+        for scheme in [SubdivisionScheme::Loop, SubdivisionScheme::CatmullClark] {
+            mesh.subdivided(scheme, 2);
+        }
+    }
+
+    #[test]
+    fn obj_crease_without_an_edge() {
+        let obj = square_obj("t crease 2/1/0 1 3 3\n");
+        let error = ControlMesh::read_obj(obj.as_bytes()).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn obj_face_with_a_repeated_vertex() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 1 2\n";
+        let error = ControlMesh::read_obj(obj.as_bytes()).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    #[should_panic(expected = "more than once")]
+    fn face_with_a_repeated_vertex() {
+        let positions = vec![
+            Point3::zero(),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ];
+        ControlMesh::new(positions, vec![vec![0, 1, 1]]);
+    }
+
+    #[test]
+    fn crease_without_an_edge_is_ignored() {
+        let obj = square_obj("");
+        let mesh = ControlMesh::read_obj(obj.as_bytes())
+            .unwrap()
+            .with_crease(1, 3, 3.0);
+        for scheme in [SubdivisionScheme::Loop, SubdivisionScheme::CatmullClark] {
+            let refined = mesh.subdivided(scheme, 2);
+            assert!(refined.positions().iter().all(|p| p.x().is_finite()));
+        }
+    }
+}
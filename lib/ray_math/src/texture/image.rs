@@ -0,0 +1,221 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+use crate::{Color, Point3};
+
+use super::Texture;
+
+/// A texture looked up from an image by UV coordinate, with `(0, 0)` at the
+/// bottom left corner. Pixel values are used as they are stored, without any
+/// gamma conversion.
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Image {
+    /// `pixels` are ordered row by row from the top of the image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` does not hold one color per pixel
+    pub fn new(width: usize, height: usize, pixels: Vec<Color>) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width * height,
+            "Image pixel count does not match its size"
+        );
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Loads a PPM image, either plain text (`P3`, as written by the renderer)
+    /// or binary (`P6`).
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn read<R: BufRead>(mut reader: R) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        // The header is four whitespace separated fields, possibly with
+        // comments. It's read a byte at a time so that for P6 only the single
+        // whitespace byte after the last field is consumed, whatever it is
+        let mut fields = Vec::with_capacity(4);
+        for _ in 0..4 {
+            fields.push(header_field(&mut reader)?.ok_or_else(|| invalid("Truncated PPM header"))?);
+        }
+
+        let mut numbers = [0usize; 3];
+        for (number, field) in numbers.iter_mut().zip(&fields[1..]) {
+            *number = field
+                .parse()
+                .map_err(|_| invalid("Invalid PPM image size"))?;
+        }
+        let [width, height, max_value] = numbers;
+        if max_value == 0 || max_value > 255 {
+            return Err(invalid("Unsupported PPM maximum value"));
+        }
+        let sample_count = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or_else(|| invalid("PPM image too large"))?;
+
+        let samples: Vec<u8> = match fields[0].as_str() {
+            "P3" => {
+                let mut text = String::new();
+                reader.read_to_string(&mut text)?;
+                text.split_whitespace()
+                    .map(|field| field.parse())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| invalid("Invalid PPM pixel value"))?
+            }
+            "P6" => {
+                let mut bytes = Vec::new();
+                reader.take(sample_count as u64).read_to_end(&mut bytes)?;
+                bytes
+            }
+            _ => return Err(invalid("Not a P3 or P6 PPM image")),
+        };
+        if samples.len() < sample_count {
+            return Err(invalid("Truncated PPM pixel data"));
+        }
+
+        let scale = 1.0 / max_value as f64;
+        let pixels = samples[..sample_count]
+            .chunks(3)
+            .map(|rgb| {
+                Color::new(
+                    rgb[0] as f64 * scale,
+                    rgb[1] as f64 * scale,
+                    rgb[2] as f64 * scale,
+                )
+            })
+            .collect();
+        Ok(Self::new(width, height, pixels))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+/// Longest PPM header field accepted, far more than any real size needs.
+const MAX_HEADER_FIELD: usize = 32;
+
+/// The next whitespace separated field of a PPM header, skipping comments
+/// (from `#` to the end of the line), or `None` at the end of the input. The
+/// whitespace byte ending the field is consumed, but nothing after it.
+fn header_field<R: BufRead>(reader: &mut R) -> io::Result<Option<String>> {
+    let mut next_byte = || -> io::Result<Option<u8>> {
+        let byte = reader.fill_buf()?.first().copied();
+        if byte.is_some() {
+            reader.consume(1);
+        }
+        Ok(byte)
+    };
+
+    let mut field = Vec::new();
+    while let Some(byte) = next_byte()? {
+        if byte == b'#' {
+            while !matches!(next_byte()?, None | Some(b'\n')) {}
+            if !field.is_empty() {
+                break;
+            }
+        } else if byte.is_ascii_whitespace() {
+            if !field.is_empty() {
+                break;
+            }
+        } else if field.len() < MAX_HEADER_FIELD {
+            field.push(byte);
+        } else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "PPM header field too long",
+            ));
+        }
+    }
+
+    Ok(if field.is_empty() {
+        None
+    } else {
+        Some(String::from_utf8_lossy(&field).into_owned())
+    })
+}
+
+impl Texture for Image {
+    fn value(&self, uv: (f64, f64), _point: &Point3) -> Color {
+        if self.pixels.is_empty() {
+            return Color::zero();
+        }
+
+        let (u, v) = (uv.0.clamp(0.0, 1.0), 1.0 - uv.1.clamp(0.0, 1.0));
+        let i = ((u * self.width as f64) as usize).min(self.width - 1);
+        let j = ((v * self.height as f64) as usize).min(self.height - 1);
+        self.pixels[j * self.width + i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_error(bytes: &[u8]) -> io::ErrorKind {
+        Image::read(bytes).err().unwrap().kind()
+    }
+
+    #[test]
+    fn reads_plain_text() {
+        let image = Image::read(&b"P3\n# A comment\n2 1\n255\n255 0 0  0 0 255\n"[..]).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 1));
+        assert_eq!(
+            image.pixels,
+            vec![Color::new(1.0, 0.0, 0.0), Color::new(0.0, 0.0, 1.0)]
+        );
+    }
+
+    #[test]
+    fn reads_binary_after_a_single_whitespace_byte() {
+        // Pixel data that looks like whitespace mustn't be skipped as part of
+        // the header
+        for separator in [b' ', b'\t', b'\n'] {
+            let mut bytes = b"P6 1 1 # size\n255".to_vec();
+            bytes.push(separator);
+            bytes.extend_from_slice(&[b' ', b'\n', 255]);
+            let image = Image::read(&bytes[..]).unwrap();
+            let expected = Color::new(32.0 / 255.0, 10.0 / 255.0, 1.0);
+            assert_eq!(image.pixels, vec![expected]);
+        }
+    }
+
+    #[test]
+    fn truncated_header() {
+        assert_eq!(read_error(b"P6 2 # 2\n"), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn truncated_pixels() {
+        assert_eq!(
+            read_error(b"P6 2 2 255\n\0\0\0"),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(read_error(b"P3 1 1 255\n1 2"), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn overflowing_size() {
+        let header = format!("P6 {} {} 255\n", usize::MAX, 2);
+        assert_eq!(read_error(header.as_bytes()), io::ErrorKind::InvalidData);
+    }
+}
@@ -1,9 +1,12 @@
 mod checkered;
+mod image;
 mod noise;
 mod solid_color;
+#[allow(clippy::module_inception)]
 mod texture;
 
 pub use checkered::*;
+pub use image::*;
 pub use noise::*;
 pub use solid_color::*;
 pub use texture::*;
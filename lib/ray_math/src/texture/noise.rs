@@ -25,6 +25,27 @@ impl Texture for Noise {
     }
 }
 
+/// Perlin turbulence as a gray level, e.g. for procedural bump maps.
+pub struct Turbulence {
+    scale: f64,
+    perlin: Perlin,
+}
+
+impl Turbulence {
+    pub fn new(rng: &mut dyn rand::RngCore, scale: f64) -> Self {
+        Self {
+            scale,
+            perlin: Perlin::new(rng),
+        }
+    }
+}
+
+impl Texture for Turbulence {
+    fn value(&self, _uv: (f64, f64), point: &Point3) -> Color {
+        Color::one() * self.perlin.turbulance(&(self.scale * *point), 7)
+    }
+}
+
 pub struct Perlin {
     random: Vec<Vec3>,
     perm_x: Vec<i32>,
@@ -56,13 +77,13 @@ impl Perlin {
         let k = point.z().floor() as i32;
 
         let mut c = [[[Vec3::zero(); 2]; 2]; 2];
-        for di in 0..2 {
-            for dj in 0..2 {
-                for dk in 0..2 {
+        for (di, plane) in c.iter_mut().enumerate() {
+            for (dj, row) in plane.iter_mut().enumerate() {
+                for (dk, cell) in row.iter_mut().enumerate() {
                     let q = self.perm_x[((i + di as i32) & 255) as usize];
                     let r = self.perm_y[((j + dj as i32) & 255) as usize];
                     let s = self.perm_z[((k + dk as i32) & 255) as usize];
-                    c[di][dj][dk] = self.random[(q ^ r ^ s) as usize];
+                    *cell = self.random[(q ^ r ^ s) as usize];
                 }
             }
         }
@@ -90,7 +111,7 @@ impl Perlin {
         p
     }
 
-    fn permute(rng: &mut dyn rand::RngCore, p: &mut Vec<i32>, n: usize) {
+    fn permute(rng: &mut dyn rand::RngCore, p: &mut [i32], n: usize) {
         for i in (1..n).rev() {
             let target = rng.gen_range(0..=i);
             p.swap(i, target);
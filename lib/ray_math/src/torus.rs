@@ -0,0 +1,155 @@
+use std::{f64, ops::Range, sync::Arc};
+
+use crate::{
+    disk::{azimuth_tangent, azimuth_u, azimuth_u_scale},
+    material::Material,
+    solve_quadratic, solve_quartic, Aabb, HitResult, Hittable, Point3, Ray, Transform, Vec3,
+};
+
+/// A ring around the Y axis, centered on the transform's position. The center
+/// of the tube is `major_radius` from the axis, and the tube itself has radius
+/// `minor_radius`.
+pub struct Torus<T> {
+    transform: T,
+    major_radius: f64,
+    minor_radius: f64,
+    material: Arc<dyn Material>,
+}
+
+impl<T: Transform> Torus<T> {
+    pub fn from(
+        transform: T,
+        major_radius: f64,
+        minor_radius: f64,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            transform,
+            major_radius,
+            minor_radius,
+            material,
+        }
+    }
+
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    pub fn major_radius(&self) -> f64 {
+        self.major_radius
+    }
+
+    pub fn minor_radius(&self) -> f64 {
+        self.minor_radius
+    }
+
+    /// `u` is the angle around the Y axis and `v` the angle around the tube,
+    /// starting from its inner edge, both in [0,1].
+    fn get_uv(&self, local: &Point3) -> (f64, f64) {
+        let distance_from_axis = (local.x() * local.x() + local.z() * local.z()).sqrt();
+        let tube_angle = local.y().atan2(distance_from_axis - self.major_radius);
+        (
+            azimuth_u(local),
+            (tube_angle + f64::consts::PI) * 0.5 * f64::consts::FRAC_1_PI,
+        )
+    }
+}
+
+impl<T: Transform> Hittable for Torus<T> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitResult> {
+        let center = self.transform.position(ray.time());
+        let direction = ray.direction();
+        let big_r2 = self.major_radius * self.major_radius;
+        let small_r2 = self.minor_radius * self.minor_radius;
+
+        // Skip rays that miss the bounding sphere, and start the others close
+        // to the torus so the quartic's roots are well conditioned
+        let bounding_radius = self.major_radius + self.minor_radius;
+        let oc = ray.origin() - center;
+        let (entry, exit) = solve_quadratic(
+            direction.length_squared(),
+            2.0 * oc.dot(&direction),
+            oc.length_squared() - bounding_radius * bounding_radius,
+        )?;
+        if exit < t_min || t_max < entry {
+            return None;
+        }
+        let shift = entry.max(0.0);
+        let origin = oc + shift * direction;
+
+        // Substituting the ray into (|p|^2 + R^2 - r^2)^2 = 4 R^2 (x^2 + z^2),
+        // along a unit direction so the leading coefficient is one however
+        // short the ray's direction is
+        let length = direction.length();
+        let unit = direction / length;
+        let od = origin.dot(&unit);
+        let k = origin.length_squared() + big_r2 - small_r2;
+        let roots = solve_quartic(
+            1.0,
+            4.0 * od,
+            4.0 * od * od + 2.0 * k - 4.0 * big_r2 * (unit.x() * unit.x() + unit.z() * unit.z()),
+            4.0 * od * k - 8.0 * big_r2 * (origin.x() * unit.x() + origin.z() * unit.z()),
+            k * k - 4.0 * big_r2 * (origin.x() * origin.x() + origin.z() * origin.z()),
+        );
+
+        let t = roots
+            .iter()
+            .map(|root| root / length + shift)
+            .find(|t| t_min <= *t && *t <= t_max)?;
+
+        let local = oc + t * direction;
+        let sum = local.length_squared() + big_r2 - small_r2;
+        let outward_normal = Vec3::new(
+            local.x() * (sum - 2.0 * big_r2),
+            local.y() * sum,
+            local.z() * (sum - 2.0 * big_r2),
+        )
+        .normalized();
+
+        Some(
+            HitResult::new(
+                ray,
+                ray.at(t),
+                outward_normal,
+                t,
+                self.get_uv(&local),
+                Arc::clone(&self.material),
+            )
+            .with_tangent(azimuth_tangent(&local))
+            .with_uv_scale(
+                azimuth_u_scale(&local),
+                2.0 * f64::consts::PI * self.minor_radius,
+            ),
+        )
+    }
+
+    fn bounding_box(&self, time_range: Range<f64>) -> Option<Aabb> {
+        let outer = self.major_radius + self.minor_radius;
+        let extent = Vec3::new(outer, self.minor_radius, outer);
+        let local = Aabb::new(-extent, extent);
+        Some(Aabb::swept(&local, &self.transform, time_range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{material::Lambertian, texture::SolidColor, Color, StaticTransform};
+
+    #[test]
+    fn short_directions_hit() {
+        let material = Arc::new(Lambertian::new(Arc::new(SolidColor::new(Color::one()))));
+        let torus = Torus::from(StaticTransform::new(Point3::zero()), 1.0, 0.25, material);
+        let origin = Point3::new(-3.0, 0.1, 0.2);
+        let direction = Vec3::new(1.0, 0.0, -0.05).normalized();
+        let expected = torus
+            .hit(&Ray::new(origin, direction, 0.0), 0.0, f64::INFINITY)
+            .unwrap()
+            .point();
+        for &scale in &[1e-2, 1e-4, 1e3] {
+            let ray = Ray::new(origin, scale * direction, 0.0);
+            let hit = torus.hit(&ray, 0.0, f64::INFINITY).unwrap();
+            assert!((hit.point() - expected).length() < 1e-9, "scale {}", scale);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::Range;
+use std::{ops::Range, sync::Arc};
 
 use crate::Point3;
 
@@ -6,6 +6,13 @@ pub trait Transform: Send + Sync {
     fn position(&self, time: f64) -> Point3;
 }
 
+/// Lets several parts of a compound shape follow the same transform.
+impl<T: Transform + ?Sized> Transform for Arc<T> {
+    fn position(&self, time: f64) -> Point3 {
+        (**self).position(time)
+    }
+}
+
 pub struct StaticTransform {
     position: Point3,
 }
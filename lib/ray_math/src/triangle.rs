@@ -0,0 +1,39 @@
+use crate::{Point3, Ray};
+
+/// Möller-Trumbore ray/triangle intersection, returning `t` and the
+/// barycentric coordinates of the hit relative to the second and third
+/// vertices.
+pub(crate) fn hit_triangle(
+    ray: &Ray,
+    vertices: &[Point3; 3],
+    t_min: f64,
+    t_max: f64,
+) -> Option<(f64, (f64, f64))> {
+    let edge1 = vertices[1] - vertices[0];
+    let edge2 = vertices[2] - vertices[0];
+    let p = ray.direction().cross(&edge2);
+    let determinant = edge1.dot(&p);
+    if determinant.abs() < 1e-12 {
+        return None;
+    }
+
+    let inv_determinant = 1.0 / determinant;
+    let s = ray.origin() - vertices[0];
+    let b1 = s.dot(&p) * inv_determinant;
+    if !(0.0..=1.0).contains(&b1) {
+        return None;
+    }
+
+    let q = s.cross(&edge1);
+    let b2 = ray.direction().dot(&q) * inv_determinant;
+    if b2 < 0.0 || b1 + b2 > 1.0 {
+        return None;
+    }
+
+    let t = edge2.dot(&q) * inv_determinant;
+    if t < t_min || t_max < t {
+        return None;
+    }
+
+    Some((t, (b1, b2)))
+}
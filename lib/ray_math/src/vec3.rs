@@ -86,7 +86,7 @@ impl Vec3 {
         let ir = (255.999 * clamp(self.r(), 0.0, 0.999)) as usize;
         let ig = (255.999 * clamp(self.g(), 0.0, 0.999)) as usize;
         let ib = (255.999 * clamp(self.b(), 0.0, 0.999)) as usize;
-        write!(writer, "{} {} {}\n", ir, ig, ib)
+        writeln!(writer, "{} {} {}", ir, ig, ib)
     }
 
     pub fn random(rng: &mut dyn rand::RngCore, min: f64, max: f64) -> Vec3 {
@@ -156,7 +156,7 @@ fn clamp(v: f64, min: f64, max: f64) -> f64 {
     if v > max {
         return max;
     }
-    return v;
+    v
 }
 
 impl Index<usize> for Vec3 {
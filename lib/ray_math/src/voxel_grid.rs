@@ -0,0 +1,212 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+use crate::Point3;
+
+/// A dense 3D grid of scalar values, such as density or temperature exported
+/// from a simulation cache. Values are sampled with trilinear interpolation in
+/// grid-local coordinates, where the whole grid spans [0,1] on each axis.
+pub struct VoxelGrid {
+    resolution: [usize; 3],
+    values: Vec<f32>,
+    max_value: f64,
+}
+
+impl VoxelGrid {
+    /// `values` are ordered with X varying fastest, then Y, then Z.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any axis has no voxels, or if `values` does not hold one
+    /// value per voxel
+    pub fn new(resolution: [usize; 3], values: Vec<f32>) -> Self {
+        assert!(
+            resolution.iter().all(|&n| n > 0),
+            "Voxel grid resolution must be non-zero"
+        );
+        assert_eq!(
+            values.len(),
+            resolution[0] * resolution[1] * resolution[2],
+            "Voxel grid value count does not match its resolution"
+        );
+        let max_value = values.iter().fold(0.0f32, |acc, &v| acc.max(v)) as f64;
+        Self {
+            resolution,
+            values,
+            max_value,
+        }
+    }
+
+    /// Builds a grid by evaluating `f` at the center of every voxel, given in
+    /// grid-local coordinates.
+    pub fn from_fn(resolution: [usize; 3], f: impl Fn(&Point3) -> f64) -> Self {
+        let [nx, ny, nz] = resolution;
+        let mut values = Vec::with_capacity(nx * ny * nz);
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    let point = Point3::new(
+                        (i as f64 + 0.5) / nx as f64,
+                        (j as f64 + 0.5) / ny as f64,
+                        (k as f64 + 0.5) / nz as f64,
+                    );
+                    values.push(f(&point) as f32);
+                }
+            }
+        }
+        Self::new(resolution, values)
+    }
+
+    /// Loads a grid from a raw voxel file: a text header line
+    /// `VOXELS <nx> <ny> <nz>` followed by `nx * ny * nz` little-endian `f32`
+    /// values, X varying fastest.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn read<R: BufRead>(mut reader: R) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let mut fields = header.split_whitespace();
+        if fields.next() != Some("VOXELS") {
+            return Err(invalid("Missing VOXELS header"));
+        }
+
+        let mut resolution = [0; 3];
+        for axis in resolution.iter_mut() {
+            *axis = fields
+                .next()
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(|| invalid("Invalid voxel grid resolution"))?;
+        }
+
+        if resolution.contains(&0) {
+            return Err(invalid("Voxel grid resolution must be non-zero"));
+        }
+        let size = resolution
+            .iter()
+            .try_fold(4usize, |size, &n| size.checked_mul(n))
+            .ok_or_else(|| invalid("Voxel grid too large"))?;
+        // Read no more than the header promises, and only allocate as the
+        // data arrives, so a huge header can't exhaust memory up front
+        let mut bytes = Vec::new();
+        reader.take(size as u64).read_to_end(&mut bytes)?;
+        if bytes.len() < size {
+            return Err(invalid("Truncated voxel grid data"));
+        }
+        let values = bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+
+        Ok(Self::new(resolution, values))
+    }
+
+    pub fn resolution(&self) -> [usize; 3] {
+        self.resolution
+    }
+
+    pub fn max_value(&self) -> f64 {
+        self.max_value
+    }
+
+    /// Value of the voxel at the given integer coordinates, clamped to the
+    /// edge of the grid.
+    pub fn voxel(&self, i: isize, j: isize, k: isize) -> f64 {
+        let [nx, ny, nz] = self.resolution;
+        let i = i.clamp(0, nx as isize - 1) as usize;
+        let j = j.clamp(0, ny as isize - 1) as usize;
+        let k = k.clamp(0, nz as isize - 1) as usize;
+        self.values[(k * ny + j) * nx + i] as f64
+    }
+
+    /// Trilinearly interpolated value at `point` in grid-local coordinates.
+    pub fn sample(&self, point: &Point3) -> f64 {
+        let mut base = [0; 3];
+        let mut frac = [0.0; 3];
+        for axis in 0..3 {
+            let x = point[axis] * self.resolution[axis] as f64 - 0.5;
+            let floor = x.floor();
+            base[axis] = floor as isize;
+            frac[axis] = x - floor;
+        }
+
+        let mut result = 0.0;
+        for corner in 0..8 {
+            let offset = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1];
+            let mut weight = 1.0;
+            for axis in 0..3 {
+                weight *= if offset[axis] == 1 {
+                    frac[axis]
+                } else {
+                    1.0 - frac[axis]
+                };
+            }
+
+            if weight > 0.0 {
+                result += weight
+                    * self.voxel(
+                        base[0] + offset[0] as isize,
+                        base[1] + offset[1] as isize,
+                        base[2] + offset[2] as isize,
+                    );
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(header: &str, values: &[f32]) -> Vec<u8> {
+        let mut bytes = header.as_bytes().to_vec();
+        for value in values {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn read_error(bytes: &[u8]) -> io::ErrorKind {
+        VoxelGrid::read(bytes).err().unwrap().kind()
+    }
+
+    #[test]
+    fn reads_values() {
+        let bytes = file("VOXELS 2 1 1\n", &[0.25, 0.5]);
+        let grid = VoxelGrid::read(&bytes[..]).unwrap();
+        assert_eq!(grid.resolution(), [2, 1, 1]);
+        assert_eq!(grid.voxel(1, 0, 0), 0.5);
+        assert_eq!(grid.max_value(), 0.5);
+    }
+
+    #[test]
+    fn truncated_data() {
+        let bytes = file("VOXELS 2 2 2\n", &[1.0; 7]);
+        assert_eq!(read_error(&bytes), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn zero_axis() {
+        let bytes = file("VOXELS 2 0 2\n", &[]);
+        assert_eq!(read_error(&bytes), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn overflowing_header() {
+        let header = format!("VOXELS {} {} 2\n", usize::MAX, usize::MAX);
+        assert_eq!(read_error(&file(&header, &[])), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn huge_header() {
+        let bytes = file("VOXELS 100000 100000 100000\n", &[1.0; 16]);
+        assert_eq!(read_error(&bytes), io::ErrorKind::InvalidData);
+    }
+}